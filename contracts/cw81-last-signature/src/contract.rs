@@ -1,97 +1,165 @@
 use types::wasm::{
-    entry_point, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult, to_json_binary, BlockInfo, StdError,
+    entry_point, Binary, Bound, Deps, DepsMut, Env, MessageInfo, Order, Response, StdResult, to_json_binary, BlockInfo,
 };
-use cw81::{ValidSignatureResponse, ValidSignaturesResponse};
+use cw81::{ValidSignatureDetailedResponse, ValidSignatureResponse, ValidSignaturesResponse, VerificationError};
 use cw_utils::Expiration;
 
-use crate::{msg::{QueryMsg, InstantiateMsg, ExecureMsg}, state::{SIGNATURE_STATE, SignatureState}};
+use crate::{
+    error::ContractError,
+    msg::{register_interfaces, QueryMsg, InstantiateMsg, ExecureMsg, SignatureInfo, SignaturesResponse}, 
+    state::{OWNER, SIGNATURES, SignatureState}
+};
 
 
 pub const CONTRACT_NAME: &str = "crates:cw81-last-signature";
 pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+
 
 #[entry_point]
-pub fn instantiate(deps: DepsMut, _ : Env, _ : MessageInfo, _ : InstantiateMsg,) 
+pub fn instantiate(deps: DepsMut, _ : Env, info : MessageInfo, msg : InstantiateMsg,) 
 -> StdResult<Response> {
     //cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
-    cw22::set_contract_supported_interface(
-        deps.storage, 
-        &[cw22::ContractSupportedInterface {
-            supported_interface: cw81::INTERFACE_NAME.into(),
-            version: CONTRACT_VERSION.into()
-        }]
-    )?;
-    SIGNATURE_STATE.save(deps.storage, &SignatureState {
-        signature: Binary::default(),
-        expiration: Expiration::Never {},
-    })?;
-    
+    register_interfaces(deps.storage)?;
+    let owner = match msg.owner {
+        Some(owner) => deps.api.addr_validate(&owner)?,
+        None => info.sender,
+    };
+    OWNER.save(deps.storage, &owner)?;
     Ok(Response::default())
 }
 
 #[entry_point]
-pub fn execute(deps: DepsMut, env : Env, _: MessageInfo, msg: ExecureMsg) -> StdResult<Response> {
+pub fn execute(deps: DepsMut, env : Env, info: MessageInfo, msg: ExecureMsg) -> Result<Response, ContractError> {
+    if info.sender != OWNER.load(deps.storage)? {
+        return Err(ContractError::Unauthorized {});
+    }
     match msg {
-        ExecureMsg::SaveSignature { signature, expiration } => {
+        ExecureMsg::SaveSignature { hash, signature, expiration } => {
             
             let expiration = expiration.unwrap_or(
                 // expires after 100 block by default
                 Expiration::AtHeight( env.block.height + 100)
             );
+            if expiration.is_expired(&env.block) {
+                return Err(ContractError::AlreadyExpired {});
+            }
 
-            SIGNATURE_STATE.save(deps.storage, &SignatureState {
+            SIGNATURES.save(deps.storage, &hash, &SignatureState {
                 signature,
                 expiration,
             })?;
 
-            Ok(Response::default())
+            Ok(Response::new()
+                .add_attribute("action", "save_signature")
+                .add_attribute("hash", hash.to_base64())
+            )
+        },
+
+        ExecureMsg::RevokeSignature { hash } => {
+            if !SIGNATURES.has(deps.storage, &hash) {
+                return Err(ContractError::UnknownHash {});
+            }
+            SIGNATURES.remove(deps.storage, &hash);
+
+            Ok(Response::new()
+                .add_attribute("action", "revoke_signature")
+                .add_attribute("hash", hash.to_base64())
+            )
         }
     }
 }
 
 #[entry_point]
-pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
-    match msg {
-        QueryMsg::LastSignature { } => {
-            let state = SIGNATURE_STATE.load(deps.storage)?;
-            to_json_binary(&state.signature)
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<Binary, ContractError> {
+    let res = match msg {
+        QueryMsg::Owner {} => to_json_binary(&OWNER.load(deps.storage)?),
+
+        QueryMsg::Signature { hash } => {
+            let info = SIGNATURES
+                .may_load(deps.storage, &hash)?
+                .map(|state| SignatureInfo {
+                    hash,
+                    signature: state.signature,
+                    expiration: state.expiration,
+                });
+            to_json_binary(&info)
+        },
+
+        QueryMsg::Signatures { start_after, limit } => {
+            let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+            let start = start_after.as_deref().map(Bound::exclusive);
+
+            let signatures = SIGNATURES
+                .range(deps.storage, start, None, Order::Ascending)
+                .take(limit)
+                .map(|item| item.map(|(hash, state)| SignatureInfo {
+                    hash: hash.into(),
+                    signature: state.signature,
+                    expiration: state.expiration,
+                }))
+                .collect::<StdResult<Vec<_>>>()?;
+
+            to_json_binary(&SignaturesResponse { signatures })
+        },
+
+        QueryMsg::SupportedInterface { name } => {
+            to_json_binary(&cw22::query_supported_interface(deps.storage, &name)?)
         },
 
-        QueryMsg::ValidSignature { signature, .. } => {
+        QueryMsg::SupportedInterfaces { start_after, limit } => {
+            to_json_binary(&cw22::query_supported_interfaces(deps.storage, start_after, limit)?)
+        },
+
+        QueryMsg::Supports { name, version_req } => {
+            to_json_binary(&cw22::query_supports(deps.storage, &name, &version_req)?)
+        },
+
+        QueryMsg::ValidSignature { data, signature, .. } => {
             to_json_binary(&ValidSignatureResponse {
-                is_valid: check_signature_state(deps, &env.block, signature.to_vec()),
+                is_valid: check_signature_state(deps, &env.block, &data, &signature).is_ok(),
             })
         },
 
-        QueryMsg::ValidSignatures { signatures, .. } => {
-            if signatures.len() != 1 {
-                return Err(StdError::msg("Only one signature is supported"));
+        QueryMsg::ValidSignatureDetailed { data, signature, .. } => {
+            let result = check_signature_state(deps, &env.block, &data, &signature).map(|_| None);
+            to_json_binary(&ValidSignatureDetailedResponse::from_result(result))
+        },
+
+        QueryMsg::ValidSignatures { data, signatures, .. } => {
+            if signatures.len() != data.len() {
+                return Err(ContractError::LengthMismatch { data: data.len(), signatures: signatures.len() });
             }
-            let signature = signatures.get(0).unwrap();
-            to_json_binary(&ValidSignaturesResponse {
-                are_valid: vec![check_signature_state(deps, &env.block, signature.to_vec())],
-            })
+            let are_valid = data
+                .iter()
+                .zip(signatures.iter())
+                .map(|(hash, signature)| check_signature_state(deps, &env.block, hash, signature).is_ok())
+                .collect();
 
+            to_json_binary(&ValidSignaturesResponse { are_valid })
         }
-    }
+    }?;
+    Ok(res)
 }
 
 
+/// The data is the hash that must have been pre-approved together with the given signature
 fn check_signature_state(
     deps: Deps,
     block: &BlockInfo,
-    signature: Vec<u8>
-) -> bool {
-    let state : SignatureState = SIGNATURE_STATE.load(deps.storage).unwrap();
-    !is_expired(state.expiration, block) && state.signature.to_vec() == signature
-}
-
-
-fn is_expired(exp : Expiration, block: &BlockInfo) -> bool {
-    match exp {
-        Expiration::AtHeight(height) => block.height >= height,
-        Expiration::AtTime(time) => block.time.seconds() >= time.seconds(),
-        Expiration::Never {} => false,
+    hash: &[u8],
+    signature: &[u8]
+) -> Result<(), VerificationError> {
+    let state = SIGNATURES
+        .may_load(deps.storage, hash)?
+        .ok_or_else(|| VerificationError::UnknownCredential { credential_id: Binary::from(hash).to_base64() })?;
+    if state.signature.as_slice() != signature {
+        return Err(VerificationError::HashMismatch);
+    }
+    if state.expiration.is_expired(block) {
+        return Err(VerificationError::Expired);
     }
-}
\ No newline at end of file
+    Ok(())
+}
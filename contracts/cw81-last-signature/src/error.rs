@@ -0,0 +1,24 @@
+use thiserror::Error;
+use types::wasm::StdError;
+
+
+#[derive(Error, Debug)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Only the owner can manage the signatures")]
+    Unauthorized {},
+
+    #[error("Expiration is already in the past")]
+    AlreadyExpired {},
+
+    #[error("No signature is stored for the hash")]
+    UnknownHash {},
+
+    #[error("Got {signatures} signatures for {data} data entries")]
+    LengthMismatch {
+        data: usize,
+        signatures: usize,
+    },
+}
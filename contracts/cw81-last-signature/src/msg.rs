@@ -1,24 +1,63 @@
-use types::wasm::Binary;
+use types::wasm::{Addr, Binary};
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cw81::valid_signature_query;
+use cw81::{valid_signature_query, valid_signature_detailed_query};
+use cw22::{supported_interface_query, supports};
 use cw_utils::Expiration;
 
 #[cw_serde]
-pub struct InstantiateMsg {}
+pub struct InstantiateMsg {
+    /// Account allowed to manage the signatures. Defaults to the sender
+    pub owner: Option<String>,
+}
 
 #[cw_serde]
 pub enum ExecureMsg {
+    /// Approves the signature of the hash replacing the previous one if any
     SaveSignature {
+        hash: Binary,
         signature: Binary,
+        /// Defaults to 100 blocks
         expiration: Option<Expiration>,
     },
+    RevokeSignature {
+        hash: Binary,
+    },
+}
+
+
+#[cw_serde]
+pub struct SignatureInfo {
+    pub hash: Binary,
+    pub signature: Binary,
+    pub expiration: Expiration,
+}
+
+
+#[cw_serde]
+pub struct SignaturesResponse {
+    pub signatures: Vec<SignatureInfo>,
 }
 
+
+#[supports(cw81, cw22, multi)]
 #[valid_signature_query]
+#[valid_signature_detailed_query]
+#[supported_interface_query]
 #[cw_serde]
 #[derive(QueryResponses)]
 pub enum QueryMsg {
-    #[returns(Binary)]
-    LastSignature {},
-}
+    #[returns(Addr)]
+    Owner {},
 
+    #[returns(Option<SignatureInfo>)]
+    Signature {
+        hash: Binary,
+    },
+
+    /// Stored signatures ordered by their hash including the expired ones
+    #[returns(SignaturesResponse)]
+    Signatures {
+        start_after: Option<Binary>,
+        limit: Option<u32>,
+    },
+}
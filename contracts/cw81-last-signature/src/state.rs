@@ -1,5 +1,5 @@
 use cosmwasm_schema::cw_serde;
-use types::wasm::{Item, Binary};
+use types::wasm::{Addr, Item, Map, Binary};
 use cw_utils::Expiration;
 
 
@@ -10,4 +10,7 @@ pub struct  SignatureState {
 }
 
 
-pub static SIGNATURE_STATE : Item<SignatureState> = Item::new("s");
\ No newline at end of file
+pub static OWNER : Item<Addr> = Item::new("o");
+
+/// Pre-approved signatures keyed by the hash they sign
+pub static SIGNATURES : Map<&[u8], SignatureState> = Map::new("h");
@@ -2,10 +2,15 @@
 mod tests {
     #![allow(deprecated)]
     use types::wasm::{testing::{message_info, mock_dependencies, mock_env}};
-    use cosmwasm_std::{from_json, Binary};
-    use cw81::ValidSignatureResponse;
+    use cosmwasm_std::{from_json, Binary, Deps};
+    use cw81::{ValidSignatureDetailedResponse, ValidSignatureResponse, ValidSignaturesResponse, VerificationFailure};
+    use cw_utils::Expiration;
 
-    use crate::{contract::{instantiate, execute, query}, msg::{InstantiateMsg, ExecureMsg, QueryMsg}};
+    use crate::{
+        contract::{instantiate, execute, query}, 
+        error::ContractError,
+        msg::{InstantiateMsg, ExecureMsg, QueryMsg, SignatureInfo, SignaturesResponse}
+    };
 
 
     #[test]
@@ -16,11 +21,13 @@ mod tests {
         let info = message_info(&deps.api.addr_make("creator"), &[]);
 
 
-        instantiate(deps.as_mut(), env.clone(), info.clone(), InstantiateMsg {}).unwrap();
+        instantiate(deps.as_mut(), env.clone(), info.clone(), InstantiateMsg { owner: None }).unwrap();
 
+        let hash = Binary::from("hash".as_bytes());
         let signature = Binary::from("signature".as_bytes());
 
         let msg = ExecureMsg::SaveSignature { 
+            hash: hash.clone(),
             signature: signature.clone().to_vec().into(), 
             expiration: None 
         };
@@ -29,27 +36,153 @@ mod tests {
 
 
         let query_msg = QueryMsg::ValidSignature { 
-            signature, data: Binary::default(), payload: None 
+            signature: signature.clone(), data: hash.clone(), payload: None 
         };
 
         let query_res = query(deps.as_ref(), env.clone(), query_msg.clone()).unwrap();
         let res : ValidSignatureResponse = from_json(&query_res).unwrap();
-        assert_eq!(res.is_valid, true);
+        assert!(res.is_valid);
 
 
         let another_msg = QueryMsg::ValidSignature { 
-            signature: Binary::from("another".as_bytes()), data: Binary::default(), payload: None 
+            signature: Binary::from("another".as_bytes()), data: hash, payload: None 
         };        
         let query_res = query(deps.as_ref(), env.clone(), another_msg).unwrap();
         let res : ValidSignatureResponse = from_json(&query_res).unwrap();
-        assert_eq!(res.is_valid, false);
+        assert!(!res.is_valid);
+
+
+        let another_hash = QueryMsg::ValidSignature { 
+            signature, data: Binary::from("another".as_bytes()), payload: None 
+        };        
+        let query_res = query(deps.as_ref(), env.clone(), another_hash).unwrap();
+        let res : ValidSignatureResponse = from_json(&query_res).unwrap();
+        assert!(!res.is_valid);
 
 
         env.block.height += 101;
         let query_res = query(deps.as_ref(), env.clone(), query_msg).unwrap();
         let res : ValidSignatureResponse = from_json(&query_res).unwrap();
-        assert_eq!(res.is_valid, false);
+        assert!(!res.is_valid);
+
+    }
+
+
+    #[test]
+    fn detailed_test() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+        let info = message_info(&deps.api.addr_make("creator"), &[]);
+
+        instantiate(deps.as_mut(), env.clone(), info.clone(), InstantiateMsg { owner: None }).unwrap();
+        execute(deps.as_mut(), env.clone(), info, ExecureMsg::SaveSignature { 
+            hash: Binary::from("hash".as_bytes()),
+            signature: Binary::from("signature".as_bytes()), 
+            expiration: None 
+        }).unwrap();
+
+        let detailed = |env, hash: &str, signature: &str| -> ValidSignatureDetailedResponse {
+            from_json(query(deps.as_ref(), env, QueryMsg::ValidSignatureDetailed { 
+                signature: Binary::from(signature.as_bytes()), data: Binary::from(hash.as_bytes()), payload: None 
+            }).unwrap()).unwrap()
+        };
 
+        let res = detailed(env.clone(), "hash", "signature");
+        assert!(res.is_valid);
+        assert!(res.failure.is_none() && res.reason.is_none());
+
+        let res = detailed(env.clone(), "hash", "another");
+        assert!(!res.is_valid);
+        assert_eq!(res.failure, Some(VerificationFailure::HashMismatch));
+
+        let res = detailed(env.clone(), "unknown", "signature");
+        assert_eq!(res.failure, Some(VerificationFailure::UnknownCredential));
+
+        env.block.height += 101;
+        let res = detailed(env, "hash", "signature");
+        assert!(!res.is_valid);
+        assert_eq!(res.failure, Some(VerificationFailure::Expired));
+        assert_eq!(res.reason, Some("Signature has expired".into()));
+    }
+
+
+    #[test]
+    fn multiple_signatures_test() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+        let info = message_info(&deps.api.addr_make("creator"), &[]);
+        let stranger = message_info(&deps.api.addr_make("stranger"), &[]);
+
+        instantiate(deps.as_mut(), env.clone(), info.clone(), InstantiateMsg { owner: None }).unwrap();
+
+        let save = |hash: &str, expiration: Option<Expiration>| ExecureMsg::SaveSignature {
+            hash: Binary::from(hash.as_bytes()),
+            signature: Binary::from(format!("{hash}-signature").as_bytes()),
+            expiration,
+        };
+
+        // only the owner can approve signatures
+        let err = execute(deps.as_mut(), env.clone(), stranger.clone(), save("first", None)).unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+
+        execute(deps.as_mut(), env.clone(), info.clone(), save("first", None)).unwrap();
+        execute(deps.as_mut(), env.clone(), info.clone(), save("second", Some(Expiration::AtHeight(env.block.height + 10)))).unwrap();
+        execute(deps.as_mut(), env.clone(), info.clone(), save("third", Some(Expiration::Never {}))).unwrap();
+
+        let batch = |deps: Deps, env| -> Vec<bool> {
+            let res : ValidSignaturesResponse = from_json(query(deps, env, QueryMsg::ValidSignatures { 
+                data: ["first", "second", "third", "third"].map(|h| Binary::from(h.as_bytes())).to_vec(),
+                signatures: ["first", "second", "third", "first"].map(|h| Binary::from(format!("{h}-signature").as_bytes())).to_vec(),
+                payload: None 
+            }).unwrap()).unwrap();
+            res.are_valid
+        };
+        assert_eq!(batch(deps.as_ref(), env.clone()), vec![true, true, true, false]);
+
+        // each signature expires on its own
+        env.block.height += 50;
+        assert_eq!(batch(deps.as_ref(), env.clone()), vec![true, false, true, false]);
+
+        assert!(execute(deps.as_mut(), env.clone(), stranger, ExecureMsg::RevokeSignature { 
+            hash: Binary::from("third".as_bytes()) 
+        }).is_err());
+        execute(deps.as_mut(), env.clone(), info.clone(), ExecureMsg::RevokeSignature { 
+            hash: Binary::from("third".as_bytes()) 
+        }).unwrap();
+        assert_eq!(batch(deps.as_ref(), env.clone()), vec![true, false, false, false]);
+
+        let info_of : Option<SignatureInfo> = from_json(query(deps.as_ref(), env.clone(), QueryMsg::Signature { 
+            hash: Binary::from("third".as_bytes()) 
+        }).unwrap()).unwrap();
+        assert!(info_of.is_none());
+
+        let page : SignaturesResponse = from_json(query(deps.as_ref(), env.clone(), QueryMsg::Signatures { 
+            start_after: None, 
+            limit: Some(1) 
+        }).unwrap()).unwrap();
+        assert_eq!(page.signatures.len(), 1);
+        assert_eq!(page.signatures[0].hash, Binary::from("first".as_bytes()));
+
+        let page : SignaturesResponse = from_json(query(deps.as_ref(), env.clone(), QueryMsg::Signatures { 
+            start_after: Some(page.signatures[0].hash.clone()), 
+            limit: None 
+        }).unwrap()).unwrap();
+        assert_eq!(page.signatures.len(), 1);
+        assert_eq!(page.signatures[0].hash, Binary::from("second".as_bytes()));
+
+        let res = query(deps.as_ref(), env.clone(), QueryMsg::ValidSignatures { 
+            data: vec![Binary::from("first".as_bytes())],
+            signatures: vec![],
+            payload: None 
+        });
+        assert!(matches!(res, Err(ContractError::LengthMismatch { data: 1, signatures: 0 })));
+
+        // already expired
+        assert!(execute(deps.as_mut(), env.clone(), info.clone(), save("fourth", Some(Expiration::AtHeight(env.block.height)))).is_err());
+        // nothing to revoke
+        assert!(execute(deps.as_mut(), env, info, ExecureMsg::RevokeSignature { 
+            hash: Binary::from("third".as_bytes()) 
+        }).is_err());
     }
 
-}
\ No newline at end of file
+}
@@ -0,0 +1,274 @@
+use types::wasm::{
+    entry_point, from_json, to_json_binary, Binary, Bound, Deps, DepsMut, Env, MessageInfo, Order, Response, StdError, StdResult, 
+    Storage,
+};
+use cw81::{
+    eth_address, AuthPayload, HashMode, KeyType, SignatureVerifier, VerificationError,
+    ValidSignatureDetailedResponse, ValidSignatureResponse, ValidSignaturesResponse
+};
+
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+
+use crate::{
+    error::ContractError,
+    msg::{register_interfaces, ConfigResponse, ExecuteMsg, InstantiateMsg, KeyInfo, KeysResponse, QueryMsg, SignatureBundle, WeightedKey},
+    state::{StoredKey, ADMIN, ALLOWED_HASH_MODES, KEYS, THRESHOLD}
+};
+
+pub const CONTRACT_NAME: &str = "crates:cw81-multisig";
+pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+
+
+#[entry_point]
+pub fn instantiate(deps: DepsMut, _ : Env, info : MessageInfo, msg : InstantiateMsg,)
+-> Result<Response, ContractError> {
+    //cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    register_interfaces(deps.storage)?;
+
+    let admin = match msg.admin {
+        Some(admin) => deps.api.addr_validate(&admin)?,
+        None => info.sender,
+    };
+    ADMIN.save(deps.storage, &admin)?;
+
+    add_keys(deps.storage, msg.keys)?;
+    save_threshold(deps.storage, msg.threshold)?;
+
+    let allowed_hash_modes = msg.allowed_hash_modes.unwrap_or_default();
+    // digests chosen by the caller would let signatures of any message pass as signatures of its hash
+    if allowed_hash_modes.contains(&HashMode::Prehashed) {
+        return Err(ContractError::PrehashedNotAllowed {});
+    }
+    ALLOWED_HASH_MODES.save(deps.storage, &allowed_hash_modes)?;
+
+    Ok(Response::default())
+}
+
+
+#[entry_point]
+pub fn execute(deps: DepsMut, _ : Env, info : MessageInfo, msg : ExecuteMsg)
+-> Result<Response, ContractError> {
+    if info.sender != ADMIN.load(deps.storage)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match msg {
+        ExecuteMsg::UpdateKeys { add, remove } => {
+            for id in remove.iter() {
+                if !KEYS.has(deps.storage, id) {
+                    return Err(ContractError::UnknownKey { id: id.clone() });
+                }
+                KEYS.remove(deps.storage, id);
+            }
+            add_keys(deps.storage, add)?;
+            // the total weight might have dropped below the threshold
+            let threshold = THRESHOLD.load(deps.storage)?;
+            save_threshold(deps.storage, threshold)?;
+
+            Ok(Response::new().add_attribute("action", "update_keys"))
+        },
+
+        ExecuteMsg::UpdateThreshold { threshold } => {
+            save_threshold(deps.storage, threshold)?;
+            Ok(Response::new().add_attribute("action", "update_threshold"))
+        },
+
+        ExecuteMsg::UpdateAdmin { admin } => {
+            ADMIN.save(deps.storage, &deps.api.addr_validate(&admin)?)?;
+            Ok(Response::new().add_attribute("action", "update_admin"))
+        },
+    }
+}
+
+
+#[entry_point]
+pub fn query(deps: Deps, _: Env, msg: QueryMsg) -> Result<Binary, ContractError> {
+    let res = match msg {
+        QueryMsg::Config {} => to_json_binary(&ConfigResponse {
+            admin: ADMIN.load(deps.storage)?,
+            threshold: THRESHOLD.load(deps.storage)?,
+            total_weight: total_weight(deps.storage)?,
+            allowed_hash_modes: ALLOWED_HASH_MODES.load(deps.storage)?,
+        }),
+
+        QueryMsg::Keys { start_after, limit } => {
+            let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+            let start = start_after.as_deref().map(Bound::exclusive);
+
+            let keys = KEYS
+                .range(deps.storage, start, None, Order::Ascending)
+                .take(limit)
+                .map(|item| item.map(|(id, key)| KeyInfo {
+                    id,
+                    pubkey: key.pubkey,
+                    key_type: key.key_type,
+                    weight: key.weight,
+                }))
+                .collect::<StdResult<Vec<_>>>()?;
+
+            to_json_binary(&KeysResponse { keys })
+        },
+
+        QueryMsg::SupportedInterface { name } => {
+            to_json_binary(&cw22::query_supported_interface(deps.storage, &name)?)
+        },
+
+        QueryMsg::SupportedInterfaces { start_after, limit } => {
+            to_json_binary(&cw22::query_supported_interfaces(deps.storage, start_after, limit)?)
+        },
+
+        QueryMsg::Supports { name, version_req } => {
+            to_json_binary(&cw22::query_supports(deps.storage, &name, &version_req)?)
+        },
+
+        QueryMsg::ValidSignature { signature, data, payload } => {
+            to_json_binary(&ValidSignatureResponse {
+                is_valid: check_bundle(deps, &data, &signature, payload.as_ref()).is_ok(),
+            })
+        },
+
+        QueryMsg::ValidSignatureDetailed { signature, data, payload } => {
+            let result = check_bundle(deps, &data, &signature, payload.as_ref())
+                .map(|signers| Some(signers.join(",")));
+            to_json_binary(&ValidSignatureDetailedResponse::from_result(result))
+        },
+
+        QueryMsg::ValidSignatures { signatures, data, payload } => {
+            if signatures.len() != data.len() {
+                return Err(ContractError::LengthMismatch { data: data.len(), signatures: signatures.len() });
+            }
+            let are_valid = data
+                .iter()
+                .zip(signatures.iter())
+                .map(|(data, signature)| check_bundle(deps, data, signature, payload.as_ref()).is_ok())
+                .collect();
+
+            to_json_binary(&ValidSignaturesResponse { are_valid })
+        }
+    }?;
+    Ok(res)
+}
+
+
+/// Verifies every signature of the bundle and returns the ids of the signers if their weight meets the threshold.
+/// The ids can't contain commas so the list joined with them is unambiguous. The hash mode requested in the payload
+/// must be one of the allowed ones
+fn check_bundle(
+    deps: Deps,
+    data: &[u8],
+    signature: &[u8],
+    payload: Option<&AuthPayload>,
+) -> Result<Vec<String>, VerificationError> {
+    let bundle : SignatureBundle = from_json(signature)
+        .map_err(|e| VerificationError::MalformedSignature { reason: e.to_string() })?;
+    let hash_mode = HashMode::from_payload(payload);
+    if let Some(mode) = hash_mode {
+        if !ALLOWED_HASH_MODES.load(deps.storage)?.contains(&mode) {
+            return Err(StdError::msg(format!("Hash mode {mode:?} can't be requested")).into());
+        }
+    }
+
+    let mut signers : Vec<String> = Vec::with_capacity(bundle.signatures.len());
+    let mut weight = 0u64;
+
+    for entry in bundle.signatures {
+        if signers.contains(&entry.key_id) {
+            return Err(VerificationError::MalformedSignature {
+                reason: format!("key `{}` signed more than once", entry.key_id),
+            });
+        }
+        let key = KEYS
+            .may_load(deps.storage, &entry.key_id)?
+            .ok_or_else(|| VerificationError::UnknownCredential { credential_id: entry.key_id.clone() })?;
+
+        match hash_mode {
+            Some(hash_mode) => key.key_type.with_hash_mode(hash_mode).check(deps.api, data, &entry.signature, &key.pubkey)?,
+            None => key.key_type.check(deps.api, data, &entry.signature, &key.pubkey)?,
+        }
+
+        weight = weight
+            .checked_add(key.weight)
+            .ok_or_else(|| StdError::msg("Weight of the signers overflows"))?;
+        signers.push(entry.key_id);
+    }
+
+    let threshold = THRESHOLD.load(deps.storage)?;
+    if weight < threshold {
+        return Err(VerificationError::ThresholdNotMet { weight, threshold });
+    }
+    Ok(signers)
+}
+
+
+fn add_keys(storage: &mut dyn Storage, keys: Vec<WeightedKey>) -> Result<(), ContractError> {
+    for key in keys {
+        if KEYS.has(storage, &key.id) {
+            return Err(ContractError::DuplicateKey { id: key.id });
+        }
+        if key.id.contains(',') {
+            return Err(ContractError::InvalidKeyId { id: key.id });
+        }
+        if key.weight == 0 {
+            return Err(ContractError::ZeroWeight { id: key.id });
+        }
+        let key_type = key.key_type.unwrap_or_default();
+        // the same signer under another id would count twice towards the threshold
+        let point = key_point(key_type, &key.pubkey)
+            .ok_or_else(|| ContractError::InvalidPubkey { id: key.id.clone() })?;
+        for item in KEYS.range(storage, None, None, Order::Ascending) {
+            let (id, stored) = item?;
+            if key_point(stored.key_type, &stored.pubkey).as_ref() == Some(&point) {
+                return Err(ContractError::DuplicatePubkey { id: key.id, registered_as: id });
+            }
+        }
+        KEYS.save(storage, &key.id, &StoredKey {
+            pubkey: key.pubkey,
+            key_type,
+            weight: key.weight,
+        })?;
+    }
+    Ok(())
+}
+
+
+/// Form in which the keys are compared so that a signer can't be registered twice in different encodings.
+/// Secp256k1 and Ethereum keys are compared by the Ethereum address of the signer and uncompressed
+/// secp256r1 keys in the compressed form. Returns `None` for secp256k1 keys that aren't valid points
+fn key_point(key_type: KeyType, pubkey: &[u8]) -> Option<Vec<u8>> {
+    match (key_type, pubkey.len()) {
+        (KeyType::Secp256k1 | KeyType::Ethereum, 20) => Some(pubkey.to_vec()),
+        (KeyType::Secp256k1 | KeyType::Ethereum, 33) => {
+            let point = k256::PublicKey::from_sec1_bytes(pubkey).ok()?.to_encoded_point(false);
+            eth_address(point.as_bytes()).ok().map(|address| address.to_vec())
+        },
+        (KeyType::Secp256k1 | KeyType::Ethereum, 65) => eth_address(pubkey).ok().map(|address| address.to_vec()),
+        (_, _) => match pubkey {
+            [0x04, point @ ..] if point.len() == 64 => {
+                let (x, y) = point.split_at(32);
+                let mut compressed = vec![0x02 | (y[31] & 1)];
+                compressed.extend_from_slice(x);
+                Some(compressed)
+            },
+            _ => Some(pubkey.to_vec()),
+        },
+    }
+}
+
+
+fn save_threshold(storage: &mut dyn Storage, threshold: u64) -> Result<(), ContractError> {
+    let total_weight = total_weight(storage)?;
+    if threshold == 0 || threshold > total_weight {
+        return Err(ContractError::InvalidThreshold { threshold, total_weight });
+    }
+    THRESHOLD.save(storage, &threshold)?;
+    Ok(())
+}
+
+
+fn total_weight(storage: &dyn Storage) -> Result<u64, ContractError> {
+    KEYS.range(storage, None, None, Order::Ascending)
+        .try_fold(0u64, |total, item| total.checked_add(item?.1.weight).ok_or(ContractError::WeightOverflow {}))
+}
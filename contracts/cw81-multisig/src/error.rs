@@ -0,0 +1,64 @@
+use thiserror::Error;
+use types::wasm::StdError;
+
+
+#[derive(Error, Debug)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Interface(#[from] cw22::Cw22Error),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Key `{id}` is already registered")]
+    DuplicateKey {
+        id: String,
+    },
+
+    #[error("Public key of `{id}` is already registered as `{registered_as}`")]
+    DuplicatePubkey {
+        id: String,
+        registered_as: String,
+    },
+
+    #[error("Key `{id}` is not registered")]
+    UnknownKey {
+        id: String,
+    },
+
+    #[error("Public key of `{id}` is invalid")]
+    InvalidPubkey {
+        id: String,
+    },
+
+    #[error("Key id `{id}` must not contain commas")]
+    InvalidKeyId {
+        id: String,
+    },
+
+    #[error("Total weight of the keys doesn't fit in u64")]
+    WeightOverflow {},
+
+    #[error("Weight of key `{id}` must be greater than zero")]
+    ZeroWeight {
+        id: String,
+    },
+
+    #[error("Threshold of {threshold} must be greater than zero and not exceed the total weight of {total_weight}")]
+    InvalidThreshold {
+        threshold: u64,
+        total_weight: u64,
+    },
+
+    #[error("Prehashed data can't be requested by the callers")]
+    PrehashedNotAllowed {},
+
+    #[error("Got {signatures} signature bundles for {data} data entries")]
+    LengthMismatch {
+        data: usize,
+        signatures: usize,
+    },
+}
@@ -0,0 +1,6 @@
+pub mod contract;
+pub mod error;
+pub mod msg;
+mod state;
+#[allow(clippy::module_inception)]
+mod tests;
@@ -0,0 +1,100 @@
+use types::wasm::{Addr, Binary};
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cw81::{valid_signature_query, valid_signature_detailed_query, AuthPayload, HashMode, KeyType};
+use cw22::{supported_interface_query, supports};
+
+
+#[cw_serde]
+pub struct WeightedKey {
+    /// Identifier of the key used in the signature bundles
+    pub id: String,
+    pub pubkey: Binary,
+    /// Defaults to secp256k1
+    pub key_type: Option<KeyType>,
+    pub weight: u64,
+}
+
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    /// Defaults to the sender
+    pub admin: Option<String>,
+    pub keys: Vec<WeightedKey>,
+    pub threshold: u64,
+    /// Hash modes that callers may request with `HashExtension` in the payload. Keys hash the data
+    /// the default way of their type otherwise. `Prehashed` can't be listed
+    pub allowed_hash_modes: Option<Vec<HashMode>>,
+}
+
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Removes and then adds the keys. Replacing a key with the same id rotates it
+    UpdateKeys {
+        add: Vec<WeightedKey>,
+        remove: Vec<String>,
+    },
+    UpdateThreshold {
+        threshold: u64,
+    },
+    UpdateAdmin {
+        admin: String,
+    },
+}
+
+
+/// Signature of a single key in a bundle
+#[cw_serde]
+pub struct KeySignature {
+    pub key_id: String,
+    pub signature: Binary,
+}
+
+
+/// JSON encoded bundle is passed as the signature to `ValidSignature`
+#[cw_serde]
+pub struct SignatureBundle {
+    pub signatures: Vec<KeySignature>,
+}
+
+
+#[supports(cw81, cw22, multi)]
+#[valid_signature_query(AuthPayload)]
+#[valid_signature_detailed_query(AuthPayload)]
+#[supported_interface_query]
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    #[returns(ConfigResponse)]
+    Config {},
+
+    #[returns(KeysResponse)]
+    Keys {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+}
+
+
+#[cw_serde]
+pub struct ConfigResponse {
+    pub admin: Addr,
+    pub threshold: u64,
+    pub total_weight: u64,
+    pub allowed_hash_modes: Vec<HashMode>,
+}
+
+
+#[cw_serde]
+pub struct KeyInfo {
+    pub id: String,
+    pub pubkey: Binary,
+    pub key_type: KeyType,
+    pub weight: u64,
+}
+
+
+#[cw_serde]
+pub struct KeysResponse {
+    pub keys: Vec<KeyInfo>,
+}
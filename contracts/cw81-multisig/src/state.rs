@@ -0,0 +1,17 @@
+use cosmwasm_schema::cw_serde;
+use cw81::{HashMode, KeyType};
+use types::wasm::{Addr, Binary, Item, Map};
+
+
+#[cw_serde]
+pub struct StoredKey {
+    pub pubkey: Binary,
+    pub key_type: KeyType,
+    pub weight: u64,
+}
+
+
+pub static ADMIN : Item<Addr> = Item::new("a");
+pub static THRESHOLD : Item<u64> = Item::new("t");
+pub static KEYS : Map<&str, StoredKey> = Map::new("k");
+pub static ALLOWED_HASH_MODES : Item<Vec<HashMode>> = Item::new("h");
@@ -0,0 +1,326 @@
+#[cfg(test)]
+mod tests {
+    use types::wasm::testing::{message_info, mock_dependencies, mock_env, MockApi, MockQuerier, MockStorage};
+    use cosmwasm_std::{from_json, to_json_binary, Binary, Env, MessageInfo, OwnedDeps};
+
+    use k256::{
+        ecdsa::{signature::DigestSigner, SigningKey, VerifyingKey, Signature},
+        elliptic_curve::rand_core::OsRng
+    };
+    use sha2::{Sha256, digest::{Update, Digest}};
+
+    use cw81::{eth_address, AuthPayload, HashExtension, HashMode, KeyType, ValidSignatureDetailedResponse, ValidSignatureResponse, ValidSignaturesResponse, VerificationFailure};
+    use crate::{
+        contract::{execute, instantiate, query},
+        error::ContractError,
+        msg::{ConfigResponse, ExecuteMsg, InstantiateMsg, KeySignature, KeysResponse, QueryMsg, SignatureBundle, WeightedKey}
+    };
+
+    type Deps = OwnedDeps<MockStorage, MockApi, MockQuerier>;
+
+
+    fn weighted_key(id: &str, secret_key: &SigningKey, weight: u64) -> WeightedKey {
+        WeightedKey {
+            id: id.into(),
+            pubkey: VerifyingKey::from(secret_key).to_encoded_point(true).as_bytes().into(),
+            key_type: None,
+            weight,
+        }
+    }
+
+    fn sign(secret_key: &SigningKey, data: &[u8]) -> Binary {
+        let signature: Signature = secret_key.sign_digest(Sha256::new().chain(data));
+        signature.to_bytes().as_slice().into()
+    }
+
+    fn bundle(signatures: Vec<(&str, Binary)>) -> Binary {
+        to_json_binary(&SignatureBundle {
+            signatures: signatures
+                .into_iter()
+                .map(|(key_id, signature)| KeySignature { key_id: key_id.into(), signature })
+                .collect(),
+        }).unwrap()
+    }
+
+    fn detailed(deps: &Deps, env: &Env, data: &Binary, signature: Binary) -> ValidSignatureDetailedResponse {
+        from_json(query(deps.as_ref(), env.clone(), QueryMsg::ValidSignatureDetailed {
+            data: data.clone(),
+            signature,
+            payload: None
+        }).unwrap()).unwrap()
+    }
+
+    /// alice and bob have a weight of 1 and carol of 2 with a threshold of 2
+    fn setup() -> (Deps, Env, MessageInfo, [SigningKey; 3]) {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = message_info(&deps.api.addr_make("creator"), &[]);
+        let keys = [
+            SigningKey::random(&mut OsRng),
+            SigningKey::random(&mut OsRng),
+            SigningKey::random(&mut OsRng),
+        ];
+
+        instantiate(deps.as_mut(), env.clone(), info.clone(), InstantiateMsg {
+            admin: None,
+            keys: vec![
+                weighted_key("alice", &keys[0], 1),
+                weighted_key("bob", &keys[1], 1),
+                weighted_key("carol", &keys[2], 2),
+            ],
+            threshold: 2,
+            allowed_hash_modes: Some(vec![HashMode::Keccak256]),
+        }).unwrap();
+
+        (deps, env, info, keys)
+    }
+
+
+    #[test]
+    fn threshold_test() {
+        let (deps, env, _, [alice, bob, carol]) = setup();
+        let data : Binary = to_json_binary("message").unwrap();
+
+        let res : ValidSignatureResponse = from_json(query(deps.as_ref(), env.clone(), QueryMsg::ValidSignature {
+            data: data.clone(),
+            signature: bundle(vec![("alice", sign(&alice, &data)), ("bob", sign(&bob, &data))]),
+            payload: None
+        }).unwrap()).unwrap();
+        assert!(res.is_valid);
+
+        let res = detailed(&deps, &env, &data, bundle(vec![("carol", sign(&carol, &data))]));
+        assert!(res.is_valid);
+        assert_eq!(res.credential_id, Some("carol".into()));
+
+        let res = detailed(&deps, &env, &data, bundle(vec![("alice", sign(&alice, &data))]));
+        assert_eq!(res.failure, Some(VerificationFailure::ThresholdNotMet));
+
+        // same key can't be counted twice
+        let res = detailed(&deps, &env, &data, bundle(vec![("alice", sign(&alice, &data)), ("alice", sign(&alice, &data))]));
+        assert_eq!(res.failure, Some(VerificationFailure::MalformedSignature));
+
+        // bob's key signing for alice
+        let res = detailed(&deps, &env, &data, bundle(vec![("alice", sign(&bob, &data)), ("bob", sign(&bob, &data))]));
+        assert_eq!(res.failure, Some(VerificationFailure::HashMismatch));
+
+        let res = detailed(&deps, &env, &data, bundle(vec![("dave", sign(&bob, &data))]));
+        assert_eq!(res.failure, Some(VerificationFailure::UnknownCredential));
+
+        // not a bundle
+        let res = detailed(&deps, &env, &data, sign(&carol, &data));
+        assert_eq!(res.failure, Some(VerificationFailure::MalformedSignature));
+    }
+
+
+    #[test]
+    fn batch_test() {
+        let (deps, env, _, [alice, bob, carol]) = setup();
+        let first : Binary = b"first".into();
+        let second : Binary = b"second".into();
+
+        let res : ValidSignaturesResponse = from_json(query(deps.as_ref(), env.clone(), QueryMsg::ValidSignatures {
+            data: vec![first.clone(), second.clone(), second.clone()],
+            signatures: vec![
+                bundle(vec![("carol", sign(&carol, &first))]),
+                bundle(vec![("alice", sign(&alice, &second)), ("bob", sign(&bob, &second))]),
+                bundle(vec![("bob", sign(&bob, &second))]),
+            ],
+            payload: None
+        }).unwrap()).unwrap();
+        assert_eq!(res.are_valid, vec![true, true, false]);
+
+        let res = query(deps.as_ref(), env, QueryMsg::ValidSignatures {
+            data: vec![first.clone(), second],
+            signatures: vec![bundle(vec![("carol", sign(&carol, &first))])],
+            payload: None
+        });
+        assert!(matches!(res, Err(ContractError::LengthMismatch { data: 2, signatures: 1 })));
+    }
+
+
+    #[test]
+    fn admin_test() {
+        let (mut deps, env, info, [alice, _, carol]) = setup();
+        let data : Binary = b"message".into();
+        let stranger = message_info(&deps.api.addr_make("stranger"), &[]);
+
+        let err = execute(deps.as_mut(), env.clone(), stranger.clone(), ExecuteMsg::UpdateThreshold { threshold: 1 }).unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+
+        let err = execute(deps.as_mut(), env.clone(), info.clone(), ExecuteMsg::UpdateThreshold { threshold: 5 }).unwrap_err();
+        assert!(matches!(err, ContractError::InvalidThreshold { threshold: 5, total_weight: 4 }));
+
+        execute(deps.as_mut(), env.clone(), info.clone(), ExecuteMsg::UpdateThreshold { threshold: 3 }).unwrap();
+        let res = detailed(&deps, &env, &data, bundle(vec![("carol", sign(&carol, &data))]));
+        assert_eq!(res.failure, Some(VerificationFailure::ThresholdNotMet));
+
+        // rotate carol's key
+        let new_carol = SigningKey::random(&mut OsRng);
+        execute(deps.as_mut(), env.clone(), info.clone(), ExecuteMsg::UpdateKeys {
+            add: vec![weighted_key("carol", &new_carol, 2)],
+            remove: vec!["carol".into()],
+        }).unwrap();
+
+        let res = detailed(&deps, &env, &data, bundle(vec![("alice", sign(&alice, &data)), ("carol", sign(&carol, &data))]));
+        assert_eq!(res.failure, Some(VerificationFailure::HashMismatch));
+        let res = detailed(&deps, &env, &data, bundle(vec![("alice", sign(&alice, &data)), ("carol", sign(&new_carol, &data))]));
+        assert!(res.is_valid);
+
+        let keys : KeysResponse = from_json(query(deps.as_ref(), env.clone(), QueryMsg::Keys {
+            start_after: Some("alice".into()),
+            limit: Some(1)
+        }).unwrap()).unwrap();
+        assert_eq!(keys.keys.len(), 1);
+        assert_eq!(keys.keys[0].id, "bob");
+
+        // hand over the control
+        execute(deps.as_mut(), env.clone(), info.clone(), ExecuteMsg::UpdateAdmin {
+            admin: stranger.sender.to_string()
+        }).unwrap();
+        let config : ConfigResponse = from_json(query(deps.as_ref(), env.clone(), QueryMsg::Config {}).unwrap()).unwrap();
+        assert_eq!(config.admin, stranger.sender);
+        assert_eq!(config.total_weight, 4);
+
+        let err = execute(deps.as_mut(), env.clone(), info, ExecuteMsg::UpdateThreshold { threshold: 1 }).unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+
+        let err = execute(deps.as_mut(), env.clone(), stranger.clone(), ExecuteMsg::UpdateKeys {
+            add: vec![weighted_key("alice", &alice, 1)],
+            remove: vec![],
+        }).unwrap_err();
+        assert!(matches!(err, ContractError::DuplicateKey { .. }));
+
+        // total weight can't drop below the threshold
+        let err = execute(deps.as_mut(), env, stranger, ExecuteMsg::UpdateKeys {
+            add: vec![],
+            remove: vec!["carol".into()],
+        }).unwrap_err();
+        assert!(matches!(err, ContractError::InvalidThreshold { .. }));
+    }
+
+
+    #[test]
+    fn invalid_keys_test() {
+        let env = mock_env();
+        let secret_key = SigningKey::random(&mut OsRng);
+
+        let try_instantiate = |keys: Vec<WeightedKey>| {
+            let mut deps = mock_dependencies();
+            let info = message_info(&deps.api.addr_make("creator"), &[]);
+            instantiate(deps.as_mut(), env.clone(), info, InstantiateMsg { admin: None, keys, threshold: 1, allowed_hash_modes: None }).unwrap_err()
+        };
+
+        let err = try_instantiate(vec![
+            weighted_key("alice", &secret_key, u64::MAX),
+            weighted_key("bob", &SigningKey::random(&mut OsRng), 1),
+        ]);
+        assert!(matches!(err, ContractError::WeightOverflow {}));
+
+        // one signer can't meet the threshold alone by holding several ids
+        let err = try_instantiate(vec![
+            weighted_key("alice", &secret_key, 1),
+            weighted_key("bob", &secret_key, 1),
+        ]);
+        assert!(matches!(err, ContractError::DuplicatePubkey { id, registered_as } if id == "bob" && registered_as == "alice"));
+
+        let mut uncompressed = weighted_key("bob", &secret_key, 1);
+        uncompressed.pubkey = VerifyingKey::from(&secret_key).to_encoded_point(false).as_bytes().into();
+        let err = try_instantiate(vec![weighted_key("alice", &secret_key, 1), uncompressed]);
+        assert!(matches!(err, ContractError::DuplicatePubkey { .. }));
+
+        // an Ethereum address is the same signer as its public key in any encoding
+        let address = eth_address(VerifyingKey::from(&secret_key).to_encoded_point(false).as_bytes()).unwrap();
+        let eth_key = WeightedKey {
+            id: "eth".into(),
+            pubkey: address.to_vec().into(),
+            key_type: Some(KeyType::Ethereum),
+            weight: 1,
+        };
+        let err = try_instantiate(vec![eth_key.clone(), weighted_key("alice", &secret_key, 1)]);
+        assert!(matches!(err, ContractError::DuplicatePubkey { id, registered_as } if id == "alice" && registered_as == "eth"));
+
+        let mut eth_pubkey = weighted_key("bob", &secret_key, 1);
+        eth_pubkey.pubkey = VerifyingKey::from(&secret_key).to_encoded_point(false).as_bytes().into();
+        eth_pubkey.key_type = Some(KeyType::Ethereum);
+        let err = try_instantiate(vec![eth_key, eth_pubkey]);
+        assert!(matches!(err, ContractError::DuplicatePubkey { id, registered_as } if id == "bob" && registered_as == "eth"));
+
+        let mut invalid = weighted_key("alice", &secret_key, 1);
+        invalid.pubkey = vec![5u8; 33].into();
+        let err = try_instantiate(vec![invalid]);
+        assert!(matches!(err, ContractError::InvalidPubkey { id } if id == "alice"));
+
+        // signers are listed in the credential id separated by commas
+        let err = try_instantiate(vec![weighted_key("alice,bob", &secret_key, 1)]);
+        assert!(matches!(err, ContractError::InvalidKeyId { id } if id == "alice,bob"));
+
+        let (mut deps, env, info, keys) = setup();
+        let err = execute(deps.as_mut(), env.clone(), info.clone(), ExecuteMsg::UpdateKeys {
+            add: vec![weighted_key("dave", &keys[0], 1)],
+            remove: vec![],
+        }).unwrap_err();
+        assert!(matches!(err, ContractError::DuplicatePubkey { id, registered_as } if id == "dave" && registered_as == "alice"));
+
+        // moving a key to another id is fine
+        execute(deps.as_mut(), env.clone(), info.clone(), ExecuteMsg::UpdateKeys {
+            add: vec![weighted_key("dave", &keys[0], 1)],
+            remove: vec!["alice".into()],
+        }).unwrap();
+
+        let err = execute(deps.as_mut(), env, info, ExecuteMsg::UpdateKeys {
+            add: vec![weighted_key("erin", &secret_key, u64::MAX)],
+            remove: vec![],
+        }).unwrap_err();
+        assert!(matches!(err, ContractError::WeightOverflow {}));
+    }
+
+
+    #[test]
+    fn hash_mode_test() {
+        let (deps, env, _, [alice, _, carol]) = setup();
+        let data : Binary = b"message".into();
+
+        let payload = |hash_mode: HashMode| Some(AuthPayload {
+            credential_id: None,
+            hrp: None,
+            extension: Some(to_json_binary(&HashExtension { hash_mode }).unwrap()),
+            delegation_depth: None,
+        });
+        let check = |data: &Binary, signature: Binary, payload: Option<AuthPayload>| -> ValidSignatureDetailedResponse {
+            from_json(query(deps.as_ref(), env.clone(), QueryMsg::ValidSignatureDetailed {
+                data: data.clone(),
+                signature,
+                payload
+            }).unwrap()).unwrap()
+        };
+
+        // allowed modes can be requested
+        let (signature, _) = carol.sign_prehash_recoverable(&HashMode::Keccak256.hash(&data)).unwrap();
+        let signature : Binary = signature.to_bytes().as_slice().into();
+        assert!(check(&data, bundle(vec![("carol", signature.clone())]), payload(HashMode::Keccak256)).is_valid);
+        assert!(!check(&data, bundle(vec![("carol", signature)]), None).is_valid);
+
+        // signatures of a message can't be passed off as signatures of its digest
+        let digest : Binary = Sha256::digest(&data).to_vec().into();
+        let signatures = bundle(vec![("alice", sign(&alice, &data)), ("carol", sign(&carol, &data))]);
+        let res = check(&digest, signatures.clone(), payload(HashMode::Prehashed));
+        assert!(!res.is_valid);
+        assert!(res.reason.unwrap().contains("can't be requested"));
+        assert!(!check(&digest, signatures.clone(), payload(HashMode::Sha256)).is_valid);
+        assert!(check(&data, signatures, None).is_valid);
+
+        let config : ConfigResponse = from_json(query(deps.as_ref(), env.clone(), QueryMsg::Config {}).unwrap()).unwrap();
+        assert_eq!(config.allowed_hash_modes, vec![HashMode::Keccak256]);
+
+        let mut deps = mock_dependencies();
+        let info = message_info(&deps.api.addr_make("creator"), &[]);
+        let err = instantiate(deps.as_mut(), env, info, InstantiateMsg {
+            admin: None,
+            keys: vec![weighted_key("alice", &alice, 1)],
+            threshold: 1,
+            allowed_hash_modes: Some(vec![HashMode::Prehashed]),
+        }).unwrap_err();
+        assert!(matches!(err, ContractError::PrehashedNotAllowed {}));
+    }
+
+}
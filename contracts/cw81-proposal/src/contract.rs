@@ -0,0 +1,128 @@
+use types::wasm::{
+    entry_point, from_json, to_json_binary, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Response, StdError, WasmMsg,
+};
+use cw81::{ValidSignatureDetailedResponse, ValidSignatureResponse, ValidSignaturesResponse, VerificationError};
+
+use crate::{
+    cw3::{Cw3QueryMsg, ProposalResponse, Status},
+    error::ContractError,
+    msg::{register_interfaces, ExecuteMsg, InstantiateMsg, ProposalSignature, QueryMsg},
+    state::{APPROVED, DAO}
+};
+
+pub const CONTRACT_NAME: &str = "crates:cw81-proposal";
+pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+
+#[entry_point]
+pub fn instantiate(deps: DepsMut, _ : Env, _ : MessageInfo, msg : InstantiateMsg,)
+-> Result<Response, ContractError> {
+    //cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    register_interfaces(deps.storage)?;
+    DAO.save(deps.storage, &deps.api.addr_validate(&msg.dao)?)?;
+    Ok(Response::default())
+}
+
+
+#[entry_point]
+pub fn execute(deps: DepsMut, _ : Env, info : MessageInfo, msg : ExecuteMsg)
+-> Result<Response, ContractError> {
+    if info.sender != DAO.load(deps.storage)? {
+        return Err(ContractError::Unauthorized {});
+    }
+    match msg {
+        ExecuteMsg::ApproveHash { hash } => {
+            APPROVED.save(deps.storage, &hash, &true)?;
+            Ok(Response::new()
+                .add_attribute("action", "approve_hash")
+                .add_attribute("hash", hash.to_base64())
+            )
+        }
+    }
+}
+
+
+#[entry_point]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<Binary, ContractError> {
+    let res = match msg {
+        QueryMsg::Dao {} => to_json_binary(&DAO.load(deps.storage)?),
+
+        QueryMsg::IsApproved { hash } => to_json_binary(&APPROVED.has(deps.storage, &hash)),
+
+        QueryMsg::SupportedInterface { name } => {
+            to_json_binary(&cw22::query_supported_interface(deps.storage, &name)?)
+        },
+
+        QueryMsg::SupportedInterfaces { start_after, limit } => {
+            to_json_binary(&cw22::query_supported_interfaces(deps.storage, start_after, limit)?)
+        },
+
+        QueryMsg::Supports { name, version_req } => {
+            to_json_binary(&cw22::query_supports(deps.storage, &name, &version_req)?)
+        },
+
+        QueryMsg::ValidSignature { data, signature, .. } => {
+            to_json_binary(&ValidSignatureResponse {
+                is_valid: check_approval(deps, &env, &data, &signature).is_ok(),
+            })
+        },
+
+        QueryMsg::ValidSignatureDetailed { data, signature, .. } => {
+            let result = check_approval(deps, &env, &data, &signature);
+            to_json_binary(&ValidSignatureDetailedResponse::from_result(result))
+        },
+
+        QueryMsg::ValidSignatures { data, signatures, .. } => {
+            if signatures.len() != data.len() {
+                return Err(ContractError::LengthMismatch { data: data.len(), signatures: signatures.len() });
+            }
+            let are_valid = data
+                .iter()
+                .zip(signatures.iter())
+                .map(|(hash, signature)| check_approval(deps, &env, hash, signature).is_ok())
+                .collect();
+
+            to_json_binary(&ValidSignaturesResponse { are_valid })
+        }
+    }?;
+    Ok(res)
+}
+
+
+/// Hashes approved by executed proposals are valid with any signature. Otherwise the signature must
+/// be `ProposalSignature` of a passed proposal that has `ApproveHash` of the hash among its messages.
+/// The id of the proposal is returned as the credential
+fn check_approval(
+    deps: Deps,
+    env: &Env,
+    hash: &[u8],
+    signature: &[u8],
+) -> Result<Option<String>, VerificationError> {
+    if APPROVED.has(deps.storage, hash) {
+        return Ok(None);
+    }
+    let ProposalSignature { proposal_id } = from_json(signature)
+        .map_err(|e| VerificationError::MalformedSignature { reason: e.to_string() })?;
+
+    let proposal : ProposalResponse = deps.querier
+        .query_wasm_smart(DAO.load(deps.storage)?, &Cw3QueryMsg::Proposal { proposal_id })
+        .map_err(|_| VerificationError::UnknownCredential { credential_id: proposal_id.to_string() })?;
+
+    if !matches!(proposal.status, Status::Passed | Status::Executed) {
+        return Err(StdError::msg(format!("Proposal {proposal_id} has not passed: {:?}", proposal.status)).into());
+    }
+    if !proposal.msgs.iter().any(|msg| approves(msg, env, hash)) {
+        return Err(VerificationError::HashMismatch);
+    }
+    Ok(Some(proposal_id.to_string()))
+}
+
+
+fn approves(msg: &CosmosMsg, env: &Env, hash: &[u8]) -> bool {
+    match msg {
+        CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, msg, .. }) if *contract_addr == env.contract.address.as_str() => {
+            matches!(from_json(msg), Ok(ExecuteMsg::ApproveHash { hash: approved }) if approved.as_slice() == hash)
+        },
+        _ => false,
+    }
+}
@@ -0,0 +1,30 @@
+//! Subset of the cw3 query interface the contract relies on.
+//! Unknown fields of the responses are ignored so that any cw3 compatible contract can be used
+use cosmwasm_schema::cw_serde;
+use types::wasm::CosmosMsg;
+
+
+#[cw_serde]
+pub enum Cw3QueryMsg {
+    Proposal { proposal_id: u64 },
+}
+
+
+#[cw_serde]
+#[derive(Copy)]
+pub enum Status {
+    Pending,
+    Open,
+    Rejected,
+    Passed,
+    Executed,
+}
+
+
+#[cw_serde]
+pub struct ProposalResponse {
+    pub id: u64,
+    pub title: String,
+    pub msgs: Vec<CosmosMsg>,
+    pub status: Status,
+}
@@ -0,0 +1,21 @@
+use thiserror::Error;
+use types::wasm::StdError;
+
+
+#[derive(Error, Debug)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Interface(#[from] cw22::Cw22Error),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Got {signatures} signatures for {data} data entries")]
+    LengthMismatch {
+        data: usize,
+        signatures: usize,
+    },
+}
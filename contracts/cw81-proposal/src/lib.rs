@@ -0,0 +1,7 @@
+pub mod contract;
+pub mod error;
+pub mod msg;
+pub mod cw3;
+mod state;
+#[allow(clippy::module_inception)]
+mod tests;
@@ -0,0 +1,45 @@
+use types::wasm::{Addr, Binary};
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cw81::{valid_signature_query, valid_signature_detailed_query};
+use cw22::{supported_interface_query, supports};
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    /// Address of the cw3 voting contract
+    pub dao: String,
+}
+
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Message to include in a proposal of the DAO. Passing the proposal is enough for
+    /// the hash to be valid and executing it makes it valid without referencing the proposal
+    ApproveHash {
+        hash: Binary,
+    },
+}
+
+
+/// Signature passed to `ValidSignature` pointing to the proposal that approved the hash
+#[cw_serde]
+pub struct ProposalSignature {
+    pub proposal_id: u64,
+}
+
+
+#[supports(cw81, cw22, multi)]
+#[valid_signature_query]
+#[valid_signature_detailed_query]
+#[supported_interface_query]
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    #[returns(Addr)]
+    Dao {},
+
+    /// Whether the hash has been approved by an executed proposal
+    #[returns(bool)]
+    IsApproved {
+        hash: Binary,
+    },
+}
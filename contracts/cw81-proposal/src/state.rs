@@ -0,0 +1,8 @@
+use types::wasm::{Addr, Item, Map};
+
+
+/// cw3 voting contract whose proposals approve the hashes
+pub static DAO : Item<Addr> = Item::new("d");
+
+/// Hashes approved by executed proposals
+pub static APPROVED : Map<&[u8], bool> = Map::new("a");
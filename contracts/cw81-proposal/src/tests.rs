@@ -0,0 +1,141 @@
+#[cfg(test)]
+mod tests {
+    use types::wasm::testing::{message_info, mock_dependencies, mock_env, MockApi, MockQuerier, MockStorage};
+    use cosmwasm_std::{
+        from_json, to_json_binary, Addr, Binary, ContractResult, CosmosMsg, Env, OwnedDeps, SystemError, SystemResult, WasmMsg, WasmQuery
+    };
+    use cw81::{ValidSignatureDetailedResponse, ValidSignaturesResponse, VerificationFailure};
+
+    use crate::{
+        contract::{execute, instantiate, query},
+        cw3::{Cw3QueryMsg, ProposalResponse, Status},
+        error::ContractError,
+        msg::{ExecuteMsg, InstantiateMsg, ProposalSignature, QueryMsg}
+    };
+
+    type Deps = OwnedDeps<MockStorage, MockApi, MockQuerier>;
+
+
+    fn approve_msg(contract: &Addr, hash: &str) -> CosmosMsg {
+        WasmMsg::Execute {
+            contract_addr: contract.to_string(),
+            msg: to_json_binary(&ExecuteMsg::ApproveHash { hash: hash.as_bytes().into() }).unwrap(),
+            funds: vec![],
+        }.into()
+    }
+
+    fn proposal_signature(proposal_id: u64) -> Binary {
+        to_json_binary(&ProposalSignature { proposal_id }).unwrap()
+    }
+
+    fn detailed(deps: &Deps, env: &Env, hash: &str, signature: Binary) -> ValidSignatureDetailedResponse {
+        from_json(query(deps.as_ref(), env.clone(), QueryMsg::ValidSignatureDetailed {
+            data: hash.as_bytes().into(),
+            signature,
+            payload: None
+        }).unwrap()).unwrap()
+    }
+
+    /// 1 passed approving "first", 2 open approving "second" and 3 passed approving "first" for another contract
+    fn setup() -> (Deps, Env, Addr) {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let dao = deps.api.addr_make("dao");
+        let other = deps.api.addr_make("other");
+
+        let proposals = [
+            (Status::Passed, approve_msg(&env.contract.address, "first")),
+            (Status::Open, approve_msg(&env.contract.address, "second")),
+            (Status::Passed, approve_msg(&other, "first")),
+        ];
+        let dao_addr = dao.to_string();
+
+        deps.querier.update_wasm(move |query| match query {
+            WasmQuery::Smart { contract_addr, msg } if *contract_addr == dao_addr => {
+                let Cw3QueryMsg::Proposal { proposal_id } = from_json(msg).unwrap();
+                match proposals.get(proposal_id as usize - 1) {
+                    Some((status, msg)) => SystemResult::Ok(ContractResult::Ok(to_json_binary(&ProposalResponse {
+                        id: proposal_id,
+                        title: format!("Proposal {proposal_id}"),
+                        msgs: vec![msg.clone()],
+                        status: *status,
+                    }).unwrap())),
+                    None => SystemResult::Ok(ContractResult::Err("Proposal not found".into())),
+                }
+            },
+            _ => SystemResult::Err(SystemError::NoSuchContract { addr: "unknown".into() }),
+        });
+
+        let info = message_info(&deps.api.addr_make("creator"), &[]);
+        instantiate(deps.as_mut(), env.clone(), info, InstantiateMsg { dao: dao.to_string() }).unwrap();
+
+        (deps, env, dao)
+    }
+
+
+    #[test]
+    fn proposal_test() {
+        let (deps, env, _) = setup();
+
+        let res = detailed(&deps, &env, "first", proposal_signature(1));
+        assert!(res.is_valid);
+        assert_eq!(res.credential_id, Some("1".into()));
+
+        // still being voted on
+        let res = detailed(&deps, &env, "second", proposal_signature(2));
+        assert_eq!(res.failure, Some(VerificationFailure::Other));
+
+        // approved for another contract
+        let res = detailed(&deps, &env, "first", proposal_signature(3));
+        assert_eq!(res.failure, Some(VerificationFailure::HashMismatch));
+
+        let res = detailed(&deps, &env, "second", proposal_signature(1));
+        assert_eq!(res.failure, Some(VerificationFailure::HashMismatch));
+
+        let res = detailed(&deps, &env, "first", proposal_signature(9));
+        assert_eq!(res.failure, Some(VerificationFailure::UnknownCredential));
+
+        let res = detailed(&deps, &env, "first", b"signature".into());
+        assert_eq!(res.failure, Some(VerificationFailure::MalformedSignature));
+
+        let res : ValidSignaturesResponse = from_json(query(deps.as_ref(), env.clone(), QueryMsg::ValidSignatures {
+            data: vec![b"first".into(), b"second".into()],
+            signatures: vec![proposal_signature(1), proposal_signature(2)],
+            payload: None
+        }).unwrap()).unwrap();
+        assert_eq!(res.are_valid, vec![true, false]);
+
+        let res = query(deps.as_ref(), env, QueryMsg::ValidSignatures {
+            data: vec![b"first".into(), b"second".into()],
+            signatures: vec![proposal_signature(1)],
+            payload: None
+        });
+        assert!(matches!(res, Err(ContractError::LengthMismatch { data: 2, signatures: 1 })));
+    }
+
+
+    #[test]
+    fn executed_proposal_test() {
+        let (mut deps, env, dao) = setup();
+
+        let stranger = message_info(&deps.api.addr_make("stranger"), &[]);
+        let err = execute(deps.as_mut(), env.clone(), stranger, ExecuteMsg::ApproveHash {
+            hash: b"second".into()
+        }).unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+
+        execute(deps.as_mut(), env.clone(), message_info(&dao, &[]), ExecuteMsg::ApproveHash {
+            hash: b"second".into()
+        }).unwrap();
+
+        let approved : bool = from_json(query(deps.as_ref(), env.clone(), QueryMsg::IsApproved {
+            hash: b"second".into()
+        }).unwrap()).unwrap();
+        assert!(approved);
+
+        // no need to reference the proposal anymore
+        assert!(detailed(&deps, &env, "second", Binary::default()).is_valid);
+        assert!(!detailed(&deps, &env, "third", Binary::default()).is_valid);
+    }
+
+}
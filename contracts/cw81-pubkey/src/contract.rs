@@ -1,80 +1,261 @@
 use types::wasm::{
-    entry_point, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult, to_json_binary,
+    entry_point, from_json, to_json_binary, Binary, Bound, Deps, DepsMut, Env, MessageInfo, Order, Response, StdError, StdResult,
+};
+use cw81::{
+    Adr036Verifier, AuthPayload, HashMode, KeyType, SignatureVerifier, VerificationError, WebAuthnVerifier,
+    ValidSignatureDetailedResponse, ValidSignatureResponse, ValidSignaturesResponse
 };
-use cw81::{ValidSignatureResponse, ValidSignaturesResponse};
 
-use crate::{msg::{QueryMsg, InstantiateMsg}, state::PUBKEY};
+use crate::{
+    msg::{register_interfaces, ExecuteMsg, InstantiateMsg, KeyHistoryEntry, KeyHistoryResponse, PubkeyExtension, QueryMsg},
+    state::{PastKey, ALLOWED_HASH_MODES, HASH_MODE, KEY_HISTORY, KEY_SINCE, KEY_TYPE, OWNER, PUBKEY, VERIFY_PAST_KEYS, WEBAUTHN}
+};
 
 pub const CONTRACT_NAME: &str = "crates:cw81-pubkey";
 pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
-use sha2::{
-    Sha256, 
-    digest::{Update, Digest}
-};
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+
 
 #[entry_point]
-pub fn instantiate(deps: DepsMut, _ : Env, _ : MessageInfo, msg : InstantiateMsg,) 
+pub fn instantiate(deps: DepsMut, env : Env, info : MessageInfo, msg : InstantiateMsg,) 
 -> StdResult<Response> {
     //cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
-    cw22::set_contract_supported_interface(
-        deps.storage, 
-        &[cw22::ContractSupportedInterface {
-            supported_interface: cw81::INTERFACE_NAME.into(),
-            version: CONTRACT_VERSION.into()
-        }]
-    )?;
+    register_interfaces(deps.storage)?;
+    let owner = match msg.owner {
+        Some(owner) => deps.api.addr_validate(&owner)?,
+        None => info.sender,
+    };
+    OWNER.save(deps.storage, &owner)?;
     PUBKEY.save(deps.storage, &msg.pubkey)?;
+    KEY_TYPE.save(deps.storage, &msg.key_type.unwrap_or_default())?;
+    KEY_SINCE.save(deps.storage, &env.block.height)?;
+    VERIFY_PAST_KEYS.save(deps.storage, &msg.verify_past_keys.unwrap_or_default())?;
+    if let Some(hash_mode) = msg.hash_mode {
+        HASH_MODE.save(deps.storage, &hash_mode)?;
+    }
+    if let Some(allowed) = msg.allowed_hash_modes {
+        // digests chosen by the caller would let signatures of any message pass as signatures of its hash
+        if allowed.contains(&HashMode::Prehashed) {
+            return Err(StdError::msg("Prehashed data can only be verified when it's the configured hash mode"));
+        }
+        ALLOWED_HASH_MODES.save(deps.storage, &allowed)?;
+    }
+    if let Some(webauthn) = msg.webauthn {
+        WEBAUTHN.save(deps.storage, &webauthn)?;
+    }
     Ok(Response::default())
 }
 
 
+#[entry_point]
+pub fn execute(deps: DepsMut, env : Env, info : MessageInfo, msg : ExecuteMsg) 
+-> StdResult<Response> {
+    if info.sender != OWNER.load(deps.storage)? {
+        return Err(StdError::msg("Only the owner can manage the keys"));
+    }
+    match msg {
+        ExecuteMsg::RotateKey { pubkey, key_type } => {
+            let current_type = KEY_TYPE.load(deps.storage)?;
+            let past = PastKey {
+                pubkey: PUBKEY.load(deps.storage)?,
+                key_type: current_type,
+                until_height: env.block.height,
+                revoked: false,
+            };
+            let since = KEY_SINCE.load(deps.storage)?;
+            KEY_HISTORY.save(deps.storage, since, &past)?;
+
+            PUBKEY.save(deps.storage, &pubkey)?;
+            KEY_TYPE.save(deps.storage, &key_type.unwrap_or(current_type))?;
+            KEY_SINCE.save(deps.storage, &env.block.height)?;
+
+            Ok(Response::new().add_attribute("action", "rotate_key"))
+        },
+
+        ExecuteMsg::RevokeKey { from_height } => {
+            let mut key = KEY_HISTORY
+                .may_load(deps.storage, from_height)?
+                .ok_or_else(|| StdError::msg(format!("No past key was activated at height {from_height}")))?;
+            key.revoked = true;
+            KEY_HISTORY.save(deps.storage, from_height, &key)?;
+
+            Ok(Response::new()
+                .add_attribute("action", "revoke_key")
+                .add_attribute("from_height", from_height.to_string())
+            )
+        }
+    }
+}
+
+
 #[entry_point]
 pub fn query(deps: Deps, _: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::PubKey {} => to_json_binary(&PUBKEY.load(deps.storage)?),
 
-        QueryMsg::ValidSignature { signature, data, .. } => {
+        QueryMsg::Owner {} => to_json_binary(&OWNER.load(deps.storage)?),
 
-            let hash = Sha256::new().chain(&data).finalize();
-            let pk: Binary = PUBKEY.load(deps.storage)?;
+        QueryMsg::KeyHistory { start_after, limit } => {
+            let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+            let start = start_after.map(Bound::exclusive);
 
-            to_json_binary(&ValidSignatureResponse {
-                is_valid: deps.api.secp256k1_verify(
-                    &hash, 
-                    &signature, 
-                    &pk
-                ).unwrap_or(false),
-            })
+            let keys = KEY_HISTORY
+                .range(deps.storage, start, None, Order::Ascending)
+                .take(limit)
+                .map(|item| item.map(|(from_height, key)| KeyHistoryEntry {
+                    from_height,
+                    until_height: key.until_height,
+                    pubkey: key.pubkey,
+                    key_type: key.key_type,
+                    revoked: key.revoked,
+                }))
+                .collect::<StdResult<Vec<_>>>()?;
+
+            to_json_binary(&KeyHistoryResponse { keys })
         },
 
-        QueryMsg::ValidSignatures { signatures, data, .. } => {
-            
-            let pk: Binary = PUBKEY.load(deps.storage).unwrap();
+        QueryMsg::SupportedInterface { name } => {
+            to_json_binary(&cw22::query_supported_interface(deps.storage, &name)?)
+        },
 
-            let are_valid : Vec<bool> = signatures
-                .iter()
-                .enumerate()
-                .map(|(i, signature)| {
+        QueryMsg::SupportedInterfaces { start_after, limit } => {
+            to_json_binary(&cw22::query_supported_interfaces(deps.storage, start_after, limit)?)
+        },
 
-                    let hash = Sha256::new().chain(
-                        data.get(i).unwrap()
-                    ).finalize();
+        QueryMsg::Supports { name, version_req } => {
+            to_json_binary(&cw22::query_supports(deps.storage, &name, &version_req)?)
+        },
 
-                    deps.api.secp256k1_verify(
-                        &hash, 
-                        &signature, 
-                        &pk
-                    ).unwrap_or(false)
+        QueryMsg::ValidSignature { signature, data, payload } => {
+            to_json_binary(&ValidSignatureResponse {
+                is_valid: check_signature(deps, &data, &signature, payload.as_ref()).is_ok(),
+            })
+        },
 
-                })
-                .collect(); 
+        QueryMsg::ValidSignatureDetailed { signature, data, payload } => {
+            let result = check_signature(deps, &data, &signature, payload.as_ref());
+            to_json_binary(&ValidSignatureDetailedResponse::from_result(result))
+        },
+
+        QueryMsg::ValidSignatures { signatures, data, payload } => {
+            let extension = parse_extension(payload.as_ref());
+            let (pk, key_type) = load_credential(deps, payload.as_ref(), extension.as_ref())?;
+            let verifier = load_verifier(deps, key_type, payload.as_ref(), extension.as_ref())?;
 
             to_json_binary(&ValidSignaturesResponse {
-                are_valid
+                are_valid: verifier.verify_batch(deps.api, &data, &signatures, &pk)?
             })
+        }
+    }
+}
+
+
+fn check_signature(
+    deps: Deps,
+    data: &[u8],
+    signature: &[u8],
+    payload: Option<&AuthPayload>,
+) -> Result<Option<String>, VerificationError> {
+    let extension = parse_extension(payload);
+    let (pk, key_type) = load_credential(deps, payload, extension.as_ref())?;
+
+    load_verifier(deps, key_type, payload, extension.as_ref())?.check(deps.api, data, signature, &pk)?;
+    Ok(Some(pk.to_base64()))
+}
 
+
+/// Keys are identified by their base64 encoding. Payloads asking for another credential are rejected
+fn load_credential(
+    deps: Deps,
+    payload: Option<&AuthPayload>,
+    extension: Option<&PubkeyExtension>,
+) -> Result<(Binary, KeyType), VerificationError> {
+    let (pk, key_type) = load_key(deps, extension)?;
+
+    if let Some(requested) = payload.and_then(|p| p.credential_id.as_ref()) {
+        if requested != &pk.to_base64() {
+            return Err(VerificationError::UnknownCredential { credential_id: requested.clone() });
         }
     }
+    Ok((pk, key_type))
+}
+
+
+/// Extensions that don't set any of the `PubkeyExtension` fields are left to the verifiers, e.g. passkey assertions
+fn parse_extension(payload: Option<&AuthPayload>) -> Option<PubkeyExtension> {
+    payload
+        .and_then(|p| p.extension.as_ref())
+        .and_then(|ext| from_json::<PubkeyExtension>(ext).ok())
+        .filter(|ext| ext.hash_mode.is_some() || ext.at_height.is_some())
 }
 
+
+/// Current key or the one that was active at the height requested in the extension if the contract
+/// verifies past keys. Revoked keys are rejected regardless of the height
+fn load_key(deps: Deps, extension: Option<&PubkeyExtension>) -> Result<(Binary, KeyType), VerificationError> {
+    let height = match extension.and_then(|ext| ext.at_height) {
+        Some(height) if height < KEY_SINCE.load(deps.storage)? => height,
+        _ => return Ok((PUBKEY.load(deps.storage)?, KEY_TYPE.load(deps.storage)?)),
+    };
+    if !VERIFY_PAST_KEYS.may_load(deps.storage)?.unwrap_or_default() {
+        return Err(StdError::msg("Verification against past keys is disabled").into());
+    }
+
+    let key = KEY_HISTORY
+        .range(deps.storage, None, Some(Bound::inclusive(height)), Order::Descending)
+        .next()
+        .transpose()?
+        .filter(|(_, key)| height < key.until_height)
+        .map(|(_, key)| key)
+        .ok_or_else(|| VerificationError::UnknownCredential { credential_id: format!("key at height {height}") })?;
+
+    if key.revoked {
+        return Err(VerificationError::Revoked { credential_id: key.pubkey.to_base64() });
+    }
+    Ok((key.pubkey, key.key_type))
+}
+
+
+/// Signatures of secp256k1 keys are checked as ADR-036 arbitrary messages when the payload has `hrp`
+/// and the ones of secp256r1 keys as passkey assertions when the payload has another `extension`.
+/// Otherwise the hash mode from the payload takes precedence over the one from the config if it's allowed
+fn load_verifier(
+    deps: Deps,
+    key_type: KeyType,
+    payload: Option<&AuthPayload>,
+    extension: Option<&PubkeyExtension>,
+) -> StdResult<Box<dyn SignatureVerifier>> {
+    match payload {
+        Some(payload) if payload.hrp.is_some() && key_type == KeyType::Secp256k1 => {
+            Ok(Box::new(Adr036Verifier::from_payload(payload)?))
+        },
+        Some(payload) if payload.extension.is_some() && extension.is_none() && key_type == KeyType::Secp256r1 => {
+            let config = WEBAUTHN
+                .may_load(deps.storage)?
+                .ok_or_else(|| StdError::msg("Passkey assertions require the webauthn settings"))?;
+            Ok(Box::new(WebAuthnVerifier::from_payload(config, payload)?))
+        },
+        _ => match load_hash_mode(deps, extension.and_then(|ext| ext.hash_mode))? {
+            Some(hash_mode) => Ok(Box::new(key_type.with_hash_mode(hash_mode))),
+            None => Ok(Box::new(key_type)),
+        },
+    }
+}
+
+
+/// Configured hash mode or the requested one if it's the same or listed in the allowed modes
+fn load_hash_mode(deps: Deps, requested: Option<HashMode>) -> StdResult<Option<HashMode>> {
+    let configured = HASH_MODE.may_load(deps.storage)?;
+    match requested {
+        Some(mode) if Some(mode) != configured => {
+            let allowed = ALLOWED_HASH_MODES.may_load(deps.storage)?.unwrap_or_default();
+            if !allowed.contains(&mode) {
+                return Err(StdError::msg(format!("Hash mode {mode:?} can't be requested")));
+            }
+            Ok(Some(mode))
+        },
+        _ => Ok(configured),
+    }
+}
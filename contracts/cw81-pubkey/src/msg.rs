@@ -1,18 +1,89 @@
-use types::wasm::Binary;
+use types::wasm::{Addr, Binary};
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cw81::valid_signature_query;
+use cw81::{valid_signature_query, valid_signature_detailed_query, AuthPayload, HashMode, KeyType, WebAuthnConfig};
+use cw22::{supported_interface_query, supports};
 
 #[cw_serde]
 pub struct InstantiateMsg {
-    pub pubkey: Binary
+    pub pubkey: Binary,
+    /// Curve of the public key. Defaults to secp256k1
+    pub key_type: Option<KeyType>,
+    /// How the data is hashed before the verification unless requested otherwise
+    /// in the payload. Defaults to the hashing of the key type
+    pub hash_mode: Option<HashMode>,
+    /// Other hash modes that callers may request in the payload. Unless listed, requests
+    /// for a mode other than `hash_mode` are rejected. `Prehashed` can't be listed
+    pub allowed_hash_modes: Option<Vec<HashMode>>,
+    /// Whether `at_height` of the payload extension can select a rotated-out key from the history.
+    /// Disabled by default since such keys keep verifying any data signed with them afterwards
+    pub verify_past_keys: Option<bool>,
+    /// Relying party settings for the passkey assertions of secp256r1 keys
+    pub webauthn: Option<WebAuthnConfig>,
+    /// Account allowed to rotate and revoke the keys. Defaults to the sender
+    pub owner: Option<String>,
 }
 
 
-#[valid_signature_query]
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Replaces the key and moves the current one to the history
+    RotateKey {
+        pubkey: Binary,
+        /// Defaults to the type of the current key
+        key_type: Option<KeyType>,
+    },
+    /// Marks a key from the history as revoked so that signatures made with it are rejected
+    /// at any height, e.g. when it has been compromised
+    RevokeKey {
+        /// Height the key has been activated at as listed in `KeyHistory`
+        from_height: u64,
+    },
+}
+
+
+/// Extension of `AuthPayload` understood by the contract. Accepts `cw81::HashExtension` as well
+#[cw_serde]
+pub struct PubkeyExtension {
+    pub hash_mode: Option<HashMode>,
+    /// Verify against the key that was active at the given block height
+    pub at_height: Option<u64>,
+}
+
+
+#[cw_serde]
+pub struct KeyHistoryEntry {
+    pub from_height: u64,
+    pub until_height: u64,
+    pub pubkey: Binary,
+    pub key_type: KeyType,
+    pub revoked: bool,
+}
+
+
+#[cw_serde]
+pub struct KeyHistoryResponse {
+    pub keys: Vec<KeyHistoryEntry>,
+}
+
+
+#[supports(cw81, cw22, multi)]
+#[valid_signature_query(AuthPayload)]
+#[valid_signature_detailed_query(AuthPayload)]
+#[supported_interface_query]
 #[cw_serde]
 #[derive(QueryResponses)]
 pub enum QueryMsg {
     #[returns(Binary)]
     PubKey {},
+
+    #[returns(Addr)]
+    Owner {},
+
+    /// Keys that have been rotated out ordered by the height they were activated at
+    #[returns(KeyHistoryResponse)]
+    KeyHistory {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
 }
 
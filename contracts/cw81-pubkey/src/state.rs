@@ -1,2 +1,29 @@
-use types::wasm::{Item, Binary};
-pub static PUBKEY : Item<Binary> = Item::new("s");
\ No newline at end of file
+use cosmwasm_schema::cw_serde;
+use types::wasm::{Addr, Binary, Item, Map};
+use cw81::{HashMode, KeyType, WebAuthnConfig};
+
+
+/// Key that has been rotated out. Was active from the height it's stored under until `until_height` (exclusive)
+#[cw_serde]
+pub struct PastKey {
+    pub pubkey: Binary,
+    pub key_type: KeyType,
+    pub until_height: u64,
+    /// Revoked keys are rejected at any height
+    #[serde(default)]
+    pub revoked: bool,
+}
+
+
+pub static PUBKEY : Item<Binary> = Item::new("s");
+pub static KEY_TYPE : Item<KeyType> = Item::new("k");
+pub static WEBAUTHN : Item<WebAuthnConfig> = Item::new("w");
+pub static HASH_MODE : Item<HashMode> = Item::new("h");
+/// Hash modes that can be requested in the payload besides the configured one
+pub static ALLOWED_HASH_MODES : Item<Vec<HashMode>> = Item::new("m");
+pub static OWNER : Item<Addr> = Item::new("o");
+/// Height since which the current key is active
+pub static KEY_SINCE : Item<u64> = Item::new("i");
+pub static KEY_HISTORY : Map<u64, PastKey> = Map::new("p");
+/// Whether the payload can ask for a key from the history
+pub static VERIFY_PAST_KEYS : Item<bool> = Item::new("v");
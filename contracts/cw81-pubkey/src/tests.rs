@@ -3,7 +3,7 @@ mod tests {
     #![allow(deprecated)]
     use types::wasm::testing::{message_info, mock_dependencies, mock_env};
     use cosmwasm_std::{
-        from_json, to_json_binary, Binary
+        from_json, to_json_binary, Binary, Deps
     };
 
     use k256::{
@@ -19,8 +19,14 @@ mod tests {
         digest::{Update, Digest}
     };
 
-    use cw81::ValidSignatureResponse;
-    use crate::{contract::{instantiate, query}, msg::{InstantiateMsg, QueryMsg}};
+    use cw81::{
+        ValidSignaturesResponse, base64url, Adr036Verifier, AuthPayload, HashExtension, HashMode, KeyType, PasskeyExtension,
+        ValidSignatureDetailedResponse, ValidSignatureResponse, VerificationFailure, WebAuthnConfig
+    };
+    use crate::{
+        contract::{execute, instantiate, query},
+        msg::{ExecuteMsg, InstantiateMsg, KeyHistoryResponse, PubkeyExtension, QueryMsg}
+    };
 
 
     #[test]
@@ -38,7 +44,13 @@ mod tests {
 
         // user store public key
         instantiate(deps.as_mut(), env.clone(), info.clone(), InstantiateMsg {
-            pubkey: public_key.to_encoded_point(false).as_bytes().into()
+            pubkey: public_key.to_encoded_point(false).as_bytes().into(),
+            key_type: None,
+            hash_mode: None,
+            allowed_hash_modes: None,
+            verify_past_keys: None,
+            webauthn: None,
+            owner: None
         }).unwrap();
 
         // dapp asks user to sign message
@@ -58,7 +70,7 @@ mod tests {
         // dapp verifies signature from the contract
         let query_res = query(deps.as_ref(), env.clone(), query_msg.clone()).unwrap();
         let res : ValidSignatureResponse = from_json(&query_res).unwrap();
-        assert_eq!(res.is_valid, true);
+        assert!(res.is_valid);
 
 
         // if users has another key the signature is wrong
@@ -72,7 +84,636 @@ mod tests {
         };      
         let query_res = query(deps.as_ref(), env.clone(), another_msg).unwrap();
         let res : ValidSignatureResponse = from_json(&query_res).unwrap();
-        assert_eq!(res.is_valid, false);
+        assert!(!res.is_valid);
+    }
+
+
+    #[test]
+    fn adr036_signature_test() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = message_info(&deps.api.addr_make("creator"), &[]);
+
+        let secret_key = SigningKey::random(&mut OsRng);
+        let public_key = VerifyingKey::from(&secret_key).to_encoded_point(true);
+
+        instantiate(deps.as_mut(), env.clone(), info, InstantiateMsg {
+            pubkey: public_key.as_bytes().into(),
+            key_type: None,
+            hash_mode: None,
+            allowed_hash_modes: None,
+            verify_past_keys: None,
+            webauthn: None,
+            owner: None
+        }).unwrap();
+
+        // wallet signs the data with `signArbitrary` using its address on the chain
+        let data : Binary = b"message".into();
+        let doc = Adr036Verifier::new("cosmos").sign_doc(&data, public_key.as_bytes()).unwrap();
+        let signature: Signature = secret_key.sign_digest(Sha256::new().chain(&doc));
+
+        let payload = AuthPayload {
+            hrp: Some("cosmos".into()),
+            credential_id: None,
+            extension: None,
+            delegation_depth: None,
+        };
+
+        let res : ValidSignatureResponse = from_json(query(deps.as_ref(), env.clone(), QueryMsg::ValidSignature { 
+            signature: signature.to_bytes().as_slice().into(), 
+            data: data.clone(), 
+            payload: Some(payload)
+        }).unwrap()).unwrap();
+        assert!(res.is_valid);
+
+        // without the prefix the signature is checked directly against the data
+        let res : ValidSignatureResponse = from_json(query(deps.as_ref(), env, QueryMsg::ValidSignature { 
+            signature: signature.to_bytes().as_slice().into(), 
+            data, 
+            payload: None
+        }).unwrap()).unwrap();
+        assert!(!res.is_valid);
+    }
+
+
+    #[test]
+    fn passkey_signature_test() {
+        use p256::ecdsa::{signature::Signer, SigningKey as P256SigningKey, Signature as P256Signature};
+
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = message_info(&deps.api.addr_make("creator"), &[]);
+
+        let secret_key = P256SigningKey::random(&mut OsRng);
+        let public_key = secret_key.verifying_key().to_encoded_point(false);
+
+        instantiate(deps.as_mut(), env.clone(), info.clone(), InstantiateMsg {
+            pubkey: public_key.as_bytes().into(),
+            key_type: Some(KeyType::Secp256r1),
+            hash_mode: None,
+            allowed_hash_modes: None,
+            verify_past_keys: None,
+            webauthn: Some(WebAuthnConfig {
+                origin: "https://example.com".into(),
+                rp_id: "example.com".into(),
+                user_verification: true,
+            }),
+            owner: None
+        }).unwrap();
+
+        // same key without the relying party settings
+        let mut unconfigured = mock_dependencies();
+        instantiate(unconfigured.as_mut(), env.clone(), info, InstantiateMsg {
+            pubkey: public_key.as_bytes().into(),
+            key_type: Some(KeyType::Secp256r1),
+            hash_mode: None,
+            allowed_hash_modes: None,
+            verify_past_keys: None,
+            webauthn: None,
+            owner: None
+        }).unwrap();
+
+        // dapp passes the data as the challenge of `navigator.credentials.get`
+        let data : Binary = to_json_binary("message").unwrap();
+
+        let assert = |deps: Deps, origin: &str, flags: u8| {
+            let mut authenticator_data = Sha256::digest(b"example.com").to_vec();
+            authenticator_data.push(flags);
+            authenticator_data.extend_from_slice(&1u32.to_be_bytes());
+
+            let client_data_json = format!(
+                r#"{{"type":"webauthn.get","challenge":"{}","origin":"{}","crossOrigin":false}}"#,
+                base64url(&data),
+                origin
+            );
+
+            // authenticator signs its data followed by the hash of the client data
+            let mut signed = authenticator_data.clone();
+            signed.extend_from_slice(&Sha256::digest(client_data_json.as_bytes()));
+            let signature: P256Signature = secret_key.sign(&signed);
+
+            let extension = PasskeyExtension {
+                authenticator_data: authenticator_data.into(),
+                client_data_json: client_data_json.into_bytes().into(),
+            };
+
+            let res : ValidSignatureResponse = from_json(query(deps, env.clone(), QueryMsg::ValidSignature { 
+                signature: signature.to_der().as_bytes().into(), 
+                data: data.clone(), 
+                payload: Some(AuthPayload {
+                    hrp: None,
+                    credential_id: None,
+                    extension: Some(to_json_binary(&extension).unwrap()),
+                    delegation_depth: None,
+                })
+            }).unwrap()).unwrap();
+            res.is_valid
+        };
+
+        // user present and verified
+        assert!(assert(deps.as_ref(), "https://example.com", 0x05));
+        // user not verified
+        assert!(!assert(deps.as_ref(), "https://example.com", 0x01));
+        // phishing website
+        assert!(!assert(deps.as_ref(), "https://example.org", 0x05));
+        // origin and relying party can't be checked
+        assert!(!assert(unconfigured.as_ref(), "https://example.com", 0x05));
+    }
+
+
+    #[test]
+    fn hash_mode_test() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = message_info(&deps.api.addr_make("creator"), &[]);
+
+        let secret_key = SigningKey::random(&mut OsRng);
+        let public_key = VerifyingKey::from(&secret_key).to_encoded_point(false);
+
+        // wallet signs raw 32-byte digests
+        instantiate(deps.as_mut(), env.clone(), info, InstantiateMsg {
+            pubkey: public_key.as_bytes().into(),
+            key_type: None,
+            hash_mode: Some(HashMode::Prehashed),
+            allowed_hash_modes: Some(vec![HashMode::Sha256]),
+            verify_past_keys: None,
+            webauthn: None,
+            owner: None
+        }).unwrap();
+
+        let data : Binary = Sha256::digest(b"message").to_vec().into();
+        let signature: Signature = secret_key.sign_prehash_recoverable(&data).unwrap().0;
+
+        let is_valid = |data: &Binary, payload: Option<AuthPayload>| {
+            let res : ValidSignatureResponse = from_json(query(deps.as_ref(), env.clone(), QueryMsg::ValidSignature { 
+                signature: signature.to_bytes().as_slice().into(), 
+                data: data.clone(), 
+                payload
+            }).unwrap()).unwrap();
+            res.is_valid
+        };
+
+        assert!(is_valid(&data, None));
+
+        // payload overrides the configured mode
+        let payload = |hash_mode: HashMode| Some(AuthPayload {
+            hrp: None,
+            credential_id: None,
+            extension: Some(to_json_binary(&HashExtension { hash_mode }).unwrap()),
+            delegation_depth: None,
+        });
+        assert!(!is_valid(&data, payload(HashMode::Sha256)));
+        assert!(is_valid(&b"message".into(), payload(HashMode::Sha256)));
+
+        // same for the batch queries
+        let res : ValidSignaturesResponse = from_json(query(deps.as_ref(), env.clone(), QueryMsg::ValidSignatures { 
+            signatures: vec![signature.to_bytes().as_slice().into(); 2], 
+            data: vec![b"message".into(), data.clone()], 
+            payload: payload(HashMode::Sha256)
+        }).unwrap()).unwrap();
+        assert_eq!(res.are_valid, vec![true, false]);
+
+        // modes that aren't allowed can't be requested
+        assert!(!is_valid(&b"message".into(), payload(HashMode::Keccak256)));
+        let err = query(deps.as_ref(), env.clone(), QueryMsg::ValidSignatures { 
+            signatures: vec![signature.to_bytes().as_slice().into()], 
+            data: vec![b"message".into()], 
+            payload: payload(HashMode::Keccak256)
+        });
+        assert!(err.is_err());
+    }
+
+
+    #[test]
+    fn prehashed_override_test() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = message_info(&deps.api.addr_make("creator"), &[]);
+
+        let secret_key = SigningKey::random(&mut OsRng);
+        let public_key = VerifyingKey::from(&secret_key).to_encoded_point(false);
+
+        let msg = |allowed_hash_modes: Option<Vec<HashMode>>| InstantiateMsg {
+            pubkey: public_key.as_bytes().into(),
+            key_type: None,
+            hash_mode: None,
+            allowed_hash_modes,
+            verify_past_keys: None,
+            webauthn: None,
+            owner: None
+        };
+        // raw digests can't be opened up to the callers
+        assert!(instantiate(deps.as_mut(), env.clone(), info.clone(), msg(Some(vec![HashMode::Prehashed]))).is_err());
+        instantiate(deps.as_mut(), env.clone(), info, msg(Some(vec![HashMode::Keccak256]))).unwrap();
+
+        // the key signs the message as usual
+        let (signature, _) = secret_key.sign_prehash_recoverable(&Sha256::digest(b"message")).unwrap();
+        let payload = Some(AuthPayload {
+            hrp: None,
+            credential_id: None,
+            extension: Some(to_json_binary(&HashExtension { hash_mode: HashMode::Prehashed }).unwrap()),
+            delegation_depth: None,
+        });
+
+        // and the signature can't be passed off as a signature of the digest
+        let res : ValidSignatureDetailedResponse = from_json(query(deps.as_ref(), env.clone(), QueryMsg::ValidSignatureDetailed { 
+            signature: signature.to_bytes().as_slice().into(), 
+            data: Sha256::digest(b"message").to_vec().into(), 
+            payload
+        }).unwrap()).unwrap();
+        assert!(!res.is_valid);
+        assert!(res.reason.unwrap().contains("can't be requested"));
+
+        let res : ValidSignatureResponse = from_json(query(deps.as_ref(), env, QueryMsg::ValidSignature { 
+            signature: signature.to_bytes().as_slice().into(), 
+            data: b"message".into(), 
+            payload: None
+        }).unwrap()).unwrap();
+        assert!(res.is_valid);
+    }
+
+
+    #[test]
+    fn detailed_test() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = message_info(&deps.api.addr_make("creator"), &[]);
+
+        let secret_key = SigningKey::random(&mut OsRng);
+        let public_key : Binary = VerifyingKey::from(&secret_key).to_encoded_point(false).as_bytes().into();
+
+        instantiate(deps.as_mut(), env.clone(), info, InstantiateMsg {
+            pubkey: public_key.clone(),
+            key_type: None,
+            hash_mode: None,
+            allowed_hash_modes: None,
+            verify_past_keys: None,
+            webauthn: None,
+            owner: None
+        }).unwrap();
+
+        let data : Binary = to_json_binary("message").unwrap();
+        let signature: Signature = secret_key.sign_digest(Sha256::new().chain(&data));
+        let signature : Binary = signature.to_bytes().as_slice().into();
+
+        let detailed = |data: &Binary, signature: &Binary, credential_id: Option<String>| -> ValidSignatureDetailedResponse {
+            from_json(query(deps.as_ref(), env.clone(), QueryMsg::ValidSignatureDetailed { 
+                signature: signature.clone(), 
+                data: data.clone(), 
+                payload: Some(AuthPayload { credential_id, hrp: None, extension: None, delegation_depth: None })
+            }).unwrap()).unwrap()
+        };
+
+        let res = detailed(&data, &signature, None);
+        assert!(res.is_valid);
+        assert_eq!(res.credential_id, Some(public_key.to_base64()));
+
+        let res = detailed(&data, &signature, Some(public_key.to_base64()));
+        assert!(res.is_valid);
+
+        let res = detailed(&data, &signature, Some("another".into()));
+        assert_eq!(res.failure, Some(VerificationFailure::UnknownCredential));
+
+        let res = detailed(&b"another".into(), &signature, None);
+        assert_eq!(res.failure, Some(VerificationFailure::HashMismatch));
+        assert!(res.credential_id.is_none());
+
+        let res = detailed(&data, &b"signature".into(), None);
+        assert!(!res.is_valid);
+        assert_eq!(res.failure, Some(VerificationFailure::MalformedSignature));
+        assert!(res.reason.is_some());
+    }
+
+
+    #[test]
+    fn invalid_key_length_test() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = message_info(&deps.api.addr_make("creator"), &[]);
+
+        instantiate(deps.as_mut(), env.clone(), info, InstantiateMsg {
+            pubkey: vec![2u8; 33].into(),
+            key_type: Some(KeyType::Ed25519),
+            hash_mode: None,
+            allowed_hash_modes: None,
+            verify_past_keys: None,
+            webauthn: None,
+            owner: None
+        }).unwrap();
+
+        let res : ValidSignatureDetailedResponse = from_json(query(deps.as_ref(), env, QueryMsg::ValidSignatureDetailed { 
+            signature: vec![0u8; 64].into(), 
+            data: b"message".into(), 
+            payload: None
+        }).unwrap()).unwrap();
+        assert_eq!(res.failure, Some(VerificationFailure::InvalidKeyLength));
+    }
+
+
+    #[test]
+    fn key_rotation_test() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+        let info = message_info(&deps.api.addr_make("creator"), &[]);
+        let started = env.block.height;
+
+        let old_key = SigningKey::random(&mut OsRng);
+        let new_key = SigningKey::random(&mut OsRng);
+        let pubkey = |key: &SigningKey| -> Binary {
+            VerifyingKey::from(key).to_encoded_point(true).as_bytes().into()
+        };
+
+        instantiate(deps.as_mut(), env.clone(), info.clone(), InstantiateMsg {
+            pubkey: pubkey(&old_key),
+            key_type: None,
+            hash_mode: None,
+            allowed_hash_modes: None,
+            verify_past_keys: Some(true),
+            webauthn: None,
+            owner: None
+        }).unwrap();
+
+        // attestation signed with the old key
+        let data : Binary = to_json_binary("attestation").unwrap();
+        let signature: Signature = old_key.sign_digest(Sha256::new().chain(&data));
+        let signature : Binary = signature.to_bytes().as_slice().into();
+
+        env.block.height += 10;
+
+        let stranger = message_info(&deps.api.addr_make("stranger"), &[]);
+        let rotate = ExecuteMsg::RotateKey { pubkey: pubkey(&new_key), key_type: None };
+        assert!(execute(deps.as_mut(), env.clone(), stranger, rotate.clone()).is_err());
+        execute(deps.as_mut(), env.clone(), info, rotate).unwrap();
+
+        let detailed = |at_height: Option<u64>, signature: &Binary| -> ValidSignatureDetailedResponse {
+            let extension = PubkeyExtension { hash_mode: None, at_height };
+            from_json(query(deps.as_ref(), env.clone(), QueryMsg::ValidSignatureDetailed { 
+                signature: signature.clone(), 
+                data: data.clone(), 
+                payload: Some(AuthPayload { 
+                    credential_id: None, 
+                    hrp: None, 
+                    extension: Some(to_json_binary(&extension).unwrap()),
+                    delegation_depth: None 
+                })
+            }).unwrap()).unwrap()
+        };
+
+        // the old key is no longer valid
+        let res = detailed(None, &signature);
+        assert_eq!(res.failure, Some(VerificationFailure::HashMismatch));
+
+        // but was valid while it was active
+        let res = detailed(Some(started + 5), &signature);
+        assert!(res.is_valid);
+        assert_eq!(res.credential_id, Some(pubkey(&old_key).to_base64()));
+
+        // rotated at the beginning of the block
+        let res = detailed(Some(started + 10), &signature);
+        assert_eq!(res.failure, Some(VerificationFailure::HashMismatch));
+
+        // before the contract existed
+        let res = detailed(Some(started - 1), &signature);
+        assert_eq!(res.failure, Some(VerificationFailure::UnknownCredential));
+
+        let new_signature: Signature = new_key.sign_digest(Sha256::new().chain(&data));
+        let new_signature : Binary = new_signature.to_bytes().as_slice().into();
+        assert!(detailed(None, &new_signature).is_valid);
+        assert!(detailed(Some(env.block.height + 100), &new_signature).is_valid);
+
+        // batches use the same key
+        let res : ValidSignaturesResponse = from_json(query(deps.as_ref(), env.clone(), QueryMsg::ValidSignatures { 
+            signatures: vec![signature.clone(), new_signature.clone()], 
+            data: vec![data.clone(), data.clone()], 
+            payload: Some(AuthPayload { 
+                credential_id: None, 
+                hrp: None, 
+                extension: Some(to_json_binary(&PubkeyExtension { hash_mode: None, at_height: Some(started) }).unwrap()),
+                delegation_depth: None 
+            })
+        }).unwrap()).unwrap();
+        assert_eq!(res.are_valid, vec![true, false]);
+
+        let history : KeyHistoryResponse = from_json(query(deps.as_ref(), env.clone(), QueryMsg::KeyHistory { 
+            start_after: None, 
+            limit: None 
+        }).unwrap()).unwrap();
+        assert_eq!(history.keys.len(), 1);
+        assert_eq!(history.keys[0].from_height, started);
+        assert_eq!(history.keys[0].until_height, started + 10);
+        assert_eq!(history.keys[0].pubkey, pubkey(&old_key));
+        assert!(!history.keys[0].revoked);
+
+        // batches check the requested credential as well
+        let batch = |credential_id: Option<String>| query(deps.as_ref(), env.clone(), QueryMsg::ValidSignatures { 
+            signatures: vec![signature.clone()], 
+            data: vec![data.clone()], 
+            payload: Some(AuthPayload { 
+                credential_id, 
+                hrp: None, 
+                extension: Some(to_json_binary(&PubkeyExtension { hash_mode: None, at_height: Some(started) }).unwrap()),
+                delegation_depth: None 
+            })
+        });
+        assert!(batch(Some(pubkey(&old_key).to_base64())).is_ok());
+        assert!(batch(Some(pubkey(&new_key).to_base64())).is_err());
+    }
+
+
+    #[test]
+    fn key_revocation_test() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+        let info = message_info(&deps.api.addr_make("creator"), &[]);
+        let started = env.block.height;
+
+        let old_key = SigningKey::random(&mut OsRng);
+        let new_key = SigningKey::random(&mut OsRng);
+        let pubkey = |key: &SigningKey| -> Binary {
+            VerifyingKey::from(key).to_encoded_point(true).as_bytes().into()
+        };
+        let sign = |key: &SigningKey, data: &Binary| -> Binary {
+            let signature: Signature = key.sign_digest(Sha256::new().chain(data));
+            signature.to_bytes().as_slice().into()
+        };
+
+        instantiate(deps.as_mut(), env.clone(), info.clone(), InstantiateMsg {
+            pubkey: pubkey(&old_key),
+            key_type: None,
+            hash_mode: None,
+            allowed_hash_modes: None,
+            verify_past_keys: Some(true),
+            webauthn: None,
+            owner: None
+        }).unwrap();
+
+        env.block.height += 10;
+        execute(deps.as_mut(), env.clone(), info.clone(), ExecuteMsg::RotateKey { pubkey: pubkey(&new_key), key_type: None }).unwrap();
+
+        // the compromised old key signs anything while claiming an old height
+        let data : Binary = to_json_binary("forged").unwrap();
+        let payload = Some(AuthPayload { 
+            credential_id: None, 
+            hrp: None, 
+            extension: Some(to_json_binary(&PubkeyExtension { hash_mode: None, at_height: Some(started) }).unwrap()),
+            delegation_depth: None 
+        });
+        let detailed = |deps: Deps| -> ValidSignatureDetailedResponse {
+            from_json(query(deps, env.clone(), QueryMsg::ValidSignatureDetailed { 
+                signature: sign(&old_key, &data), 
+                data: data.clone(), 
+                payload: payload.clone()
+            }).unwrap()).unwrap()
+        };
+        assert!(detailed(deps.as_ref()).is_valid);
+
+        // only the owner can revoke and only the keys from the history
+        let revoke = ExecuteMsg::RevokeKey { from_height: started };
+        let stranger = message_info(&deps.api.addr_make("stranger"), &[]);
+        assert!(execute(deps.as_mut(), env.clone(), stranger, revoke.clone()).is_err());
+        assert!(execute(deps.as_mut(), env.clone(), info.clone(), ExecuteMsg::RevokeKey { from_height: started + 10 }).is_err());
+        execute(deps.as_mut(), env.clone(), info, revoke).unwrap();
+
+        let res = detailed(deps.as_ref());
+        assert_eq!(res.failure, Some(VerificationFailure::Revoked));
+
+        let res = query(deps.as_ref(), env.clone(), QueryMsg::ValidSignatures { 
+            signatures: vec![sign(&old_key, &data)], 
+            data: vec![data.clone()], 
+            payload: payload.clone()
+        });
+        assert!(res.is_err());
+
+        // the current key is unaffected
+        let res : ValidSignatureResponse = from_json(query(deps.as_ref(), env.clone(), QueryMsg::ValidSignature { 
+            signature: sign(&new_key, &data), 
+            data: data.clone(), 
+            payload: None
+        }).unwrap()).unwrap();
+        assert!(res.is_valid);
+
+        let history : KeyHistoryResponse = from_json(query(deps.as_ref(), env, QueryMsg::KeyHistory { 
+            start_after: None, 
+            limit: None 
+        }).unwrap()).unwrap();
+        assert!(history.keys[0].revoked);
+    }
+
+
+    #[test]
+    fn past_keys_disabled_test() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+        let info = message_info(&deps.api.addr_make("creator"), &[]);
+        let started = env.block.height;
+
+        let old_key = SigningKey::random(&mut OsRng);
+        let new_key = SigningKey::random(&mut OsRng);
+        let pubkey = |key: &SigningKey| -> Binary {
+            VerifyingKey::from(key).to_encoded_point(true).as_bytes().into()
+        };
+        let sign = |key: &SigningKey, data: &Binary| -> Binary {
+            let signature: Signature = key.sign_digest(Sha256::new().chain(data));
+            signature.to_bytes().as_slice().into()
+        };
+
+        instantiate(deps.as_mut(), env.clone(), info.clone(), InstantiateMsg {
+            pubkey: pubkey(&old_key),
+            key_type: None,
+            hash_mode: None,
+            allowed_hash_modes: None,
+            verify_past_keys: None,
+            webauthn: None,
+            owner: None
+        }).unwrap();
+
+        env.block.height += 10;
+        execute(deps.as_mut(), env.clone(), info, ExecuteMsg::RotateKey { pubkey: pubkey(&new_key), key_type: None }).unwrap();
+
+        let data : Binary = to_json_binary("forged").unwrap();
+        let detailed = |key: &SigningKey, at_height: u64| -> ValidSignatureDetailedResponse {
+            from_json(query(deps.as_ref(), env.clone(), QueryMsg::ValidSignatureDetailed {
+                signature: sign(key, &data),
+                data: data.clone(),
+                payload: Some(AuthPayload {
+                    credential_id: None,
+                    hrp: None,
+                    extension: Some(to_json_binary(&PubkeyExtension { hash_mode: None, at_height: Some(at_height) }).unwrap()),
+                    delegation_depth: None
+                })
+            }).unwrap()).unwrap()
+        };
+
+        // the rotated-out key can't be selected by the caller
+        let res = detailed(&old_key, started);
+        assert!(!res.is_valid);
+        assert_eq!(res.failure, Some(VerificationFailure::Other));
+
+        // heights since the rotation use the current key
+        assert!(detailed(&new_key, env.block.height).is_valid);
+    }
+
+
+    #[test]
+    fn delegated_signature_test() {
+        use types::wasm::{testing::MockQuerier, ContractResult, Empty, QuerierWrapper, SystemResult, WasmQuery};
+        use cw81::{DelegatingVerifier, SignatureVerifier, DEFAULT_MAX_DEPTH};
+
+        // nested contract holding a key that signs keccak256 digests requested through the extension
+        let mut nested = mock_dependencies();
+        let env = mock_env();
+        let info = message_info(&nested.api.addr_make("creator"), &[]);
+        let secret_key = SigningKey::random(&mut OsRng);
+
+        instantiate(nested.as_mut(), env.clone(), info, InstantiateMsg {
+            pubkey: VerifyingKey::from(&secret_key).to_encoded_point(true).as_bytes().into(),
+            key_type: None,
+            hash_mode: None,
+            allowed_hash_modes: Some(vec![HashMode::Keccak256]),
+            verify_past_keys: None,
+            webauthn: None,
+            owner: None
+        }).unwrap();
+
+        let api = nested.api;
+        let nested_addr = api.addr_make("nested");
+        let contract_addr = nested_addr.to_string();
+        let mut querier: MockQuerier = MockQuerier::new(&[]);
+        querier.update_wasm(move |request| match request {
+            WasmQuery::ContractInfo { contract_addr: addr } if *addr == contract_addr => {
+                SystemResult::Ok(ContractResult::Ok(Binary::from(
+                    br#"{"code_id":1,"creator":"creator","admin":null,"pinned":false,"ibc_port":null}"#
+                )))
+            },
+            WasmQuery::Smart { contract_addr: addr, msg } if *addr == contract_addr => {
+                SystemResult::Ok(query(nested.as_ref(), mock_env(), from_json(msg).unwrap()).into())
+            },
+            _ => panic!("unexpected query"),
+        });
+        let querier = QuerierWrapper::<Empty>::new(&querier);
+
+        let data : Binary = to_json_binary("delegated").unwrap();
+        let digest = HashMode::Keccak256.hash(&data);
+        let (signature, _) = secret_key.sign_prehash_recoverable(&digest).unwrap();
+
+        let payload = |hash_mode: HashMode, credential_id: Option<String>| AuthPayload {
+            credential_id,
+            hrp: None,
+            extension: Some(to_json_binary(&HashExtension { hash_mode }).unwrap()),
+            delegation_depth: None,
+        };
+
+        // the extension of the caller reaches the nested contract unchanged
+        let verifier = DelegatingVerifier::from_payload(querier, KeyType::Secp256k1, DEFAULT_MAX_DEPTH, Some(&payload(HashMode::Keccak256, None)));
+        assert!(verifier.verify(&api, &data, &signature.to_bytes(), nested_addr.as_bytes()).unwrap());
+
+        let verifier = DelegatingVerifier::from_payload(querier, KeyType::Secp256k1, DEFAULT_MAX_DEPTH, Some(&payload(HashMode::Sha256, None)));
+        assert!(!verifier.verify(&api, &data, &signature.to_bytes(), nested_addr.as_bytes()).unwrap());
+
+        // the credential of the caller names the nested contract and isn't passed on as a key of its own
+        let credential = Some(nested_addr.to_string());
+        let verifier = DelegatingVerifier::from_payload(querier, KeyType::Secp256k1, DEFAULT_MAX_DEPTH, Some(&payload(HashMode::Keccak256, credential)));
+        assert!(verifier.verify(&api, &data, &signature.to_bytes(), nested_addr.as_bytes()).unwrap());
     }
 
-}
\ No newline at end of file
+}
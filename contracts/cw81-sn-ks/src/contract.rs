@@ -0,0 +1,89 @@
+use cosmwasm_std::entry_point;
+use cw81::{Secp256k1Verifier, SignatureVerifier, ValidSignatureResponse, ValidSignaturesResponse};
+use k256::{elliptic_curve::sec1::ToEncodedPoint, SecretKey};
+use sha2::{Digest, Sha256};
+use types::wasm::{
+    generic_err, to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult,
+};
+
+use crate::{
+    msg::{QueryMsg, InstantiateMsg, ExecuteMsg},
+    state::{load_owner, load_privkey, load_pubkey, save_keys, save_owner}
+};
+
+
+pub const CONTRACT_NAME: &str = "crates:cw81-sn-ks";
+pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+
+#[entry_point]
+pub fn instantiate(deps: DepsMut, env : Env, info : MessageInfo, msg : InstantiateMsg,) 
+-> StdResult<Response> {
+    let random = env.block.random
+        .ok_or_else(|| generic_err("Block randomness is required to generate the key"))?;
+
+    let privkey = Sha256::new()
+        .chain_update(random.as_slice())
+        .chain_update(env.contract.address.as_bytes())
+        .chain_update(msg.entropy.unwrap_or_default().as_slice())
+        .finalize();
+    // fails for zero or values above the order of the curve that practically never come out of sha256
+    let secret_key = SecretKey::from_slice(&privkey)
+        .map_err(|_| generic_err("Generated private key is invalid"))?;
+    let pubkey = secret_key.public_key().to_encoded_point(true);
+    save_keys(deps.storage, &privkey, pubkey.as_bytes());
+
+    let owner = match msg.owner {
+        Some(owner) => deps.api.addr_validate(&owner)?,
+        None => info.sender,
+    };
+    save_owner(deps.storage, &owner);
+
+    Ok(Response::new()
+        .add_attribute("action", "instantiate")
+        .add_attribute("pubkey", Binary::from(pubkey.as_bytes()).to_base64())
+    )
+}
+
+#[entry_point]
+pub fn execute(deps: DepsMut, _ : Env, info: MessageInfo, msg: ExecuteMsg) -> StdResult<Response> {
+    if info.sender != load_owner(deps.storage)? {
+        return Err(generic_err("Only the owner can sign with the key"));
+    }
+    match msg {
+        ExecuteMsg::SignData { data } => {
+            let privkey = load_privkey(deps.storage)?;
+            let signature = deps.api
+                .secp256k1_sign(&data, &privkey)
+                .map_err(|err| generic_err(err.to_string()))?;
+
+            Ok(Response::new()
+                .add_attribute("action", "sign_data")
+                .set_data(signature)
+            )
+        }
+    }
+}
+
+#[entry_point]
+pub fn query(deps: Deps, _: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::PubKey {} => to_json_binary(&Binary::from(load_pubkey(deps.storage)?)),
+
+        QueryMsg::Owner {} => to_json_binary(&load_owner(deps.storage)?),
+
+        QueryMsg::ValidSignature { data, signature, .. } => {
+            let pubkey = load_pubkey(deps.storage)?;
+            to_json_binary(&ValidSignatureResponse {
+                is_valid: Secp256k1Verifier.is_valid(deps.api, &data, &signature, &pubkey),
+            })
+        },
+
+        QueryMsg::ValidSignatures { data, signatures, .. } => {
+            let pubkey = load_pubkey(deps.storage)?;
+            to_json_binary(&ValidSignaturesResponse {
+                are_valid: Secp256k1Verifier.verify_batch(deps.api, &data, &signatures, &pubkey)?,
+            })
+        }
+    }
+}
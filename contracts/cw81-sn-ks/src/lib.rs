@@ -0,0 +1,5 @@
+pub mod contract;
+pub mod msg;
+mod state;
+#[allow(clippy::module_inception)]
+mod tests;
@@ -0,0 +1,43 @@
+use cosmwasm_schema::{serde, schemars, QueryResponses};
+use cw81::valid_signature_query;
+use types::wasm::Binary;
+
+// secret-cosmwasm-std types don't implement `Schemaifier` required by `cw_serde` so
+// its derives are listed explicitly. Responses of `Binary` and `Addr` are described as strings
+
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, schemars::JsonSchema)]
+#[serde(crate = "cosmwasm_schema::serde", deny_unknown_fields)]
+#[schemars(crate = "cosmwasm_schema::schemars")]
+pub struct InstantiateMsg {
+    /// Account allowed to sign data with the key. Defaults to the sender
+    pub owner: Option<String>,
+    /// Extra bytes mixed into the block randomness the key is derived from
+    pub entropy: Option<Binary>,
+}
+
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, schemars::JsonSchema)]
+#[serde(crate = "cosmwasm_schema::serde", deny_unknown_fields, rename_all = "snake_case")]
+#[schemars(crate = "cosmwasm_schema::schemars")]
+pub enum ExecuteMsg {
+    /// Signs the sha256 hash of the data. The signature is returned in the data of the response
+    SignData {
+        data: Binary,
+    },
+}
+
+
+#[valid_signature_query]
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, schemars::JsonSchema, QueryResponses)]
+#[serde(crate = "cosmwasm_schema::serde", deny_unknown_fields, rename_all = "snake_case")]
+#[schemars(crate = "cosmwasm_schema::schemars")]
+pub enum QueryMsg {
+    /// Base64 encoded compressed secp256k1 public key of the contract
+    #[returns(String)]
+    PubKey {},
+
+    /// Address of the owner
+    #[returns(String)]
+    Owner {},
+}
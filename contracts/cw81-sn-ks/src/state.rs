@@ -0,0 +1,37 @@
+use types::wasm::{Addr, StdError, StdResult, Storage};
+
+
+/// Private key generated at the instantiation. Never leaves the contract
+const PRIVKEY : &[u8] = b"k";
+/// Compressed public key of the private key
+const PUBKEY : &[u8] = b"p";
+const OWNER : &[u8] = b"o";
+
+
+fn load(storage: &dyn Storage, key: &[u8]) -> StdResult<Vec<u8>> {
+    storage.get(key).ok_or_else(|| StdError::not_found(String::from_utf8_lossy(key)))
+}
+
+
+pub fn save_keys(storage: &mut dyn Storage, privkey: &[u8], pubkey: &[u8]) {
+    storage.set(PRIVKEY, privkey);
+    storage.set(PUBKEY, pubkey);
+}
+
+pub fn load_privkey(storage: &dyn Storage) -> StdResult<Vec<u8>> {
+    load(storage, PRIVKEY)
+}
+
+pub fn load_pubkey(storage: &dyn Storage) -> StdResult<Vec<u8>> {
+    load(storage, PUBKEY)
+}
+
+
+pub fn save_owner(storage: &mut dyn Storage, owner: &Addr) {
+    storage.set(OWNER, owner.as_bytes());
+}
+
+pub fn load_owner(storage: &dyn Storage) -> StdResult<Addr> {
+    let owner = String::from_utf8(load(storage, OWNER)?)?;
+    Ok(Addr::unchecked(owner))
+}
@@ -0,0 +1,113 @@
+#[cfg(test)]
+mod tests {
+    use cw81::{ValidSignatureResponse, ValidSignaturesResponse};
+    use k256::ecdsa::{signature::Verifier, Signature, VerifyingKey};
+    use types::wasm::{
+        from_json, Addr, Binary, Deps, 
+        testing::{mock_dependencies, mock_env, mock_info}
+    };
+
+    use crate::{
+        contract::{execute, instantiate, query}, 
+        msg::{ExecuteMsg, InstantiateMsg, QueryMsg}
+    };
+
+
+    #[test]
+    fn sign_and_verify_test() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = mock_info("creator", &[]);
+
+        instantiate(deps.as_mut(), env.clone(), info.clone(), InstantiateMsg { owner: None, entropy: None }).unwrap();
+
+        let pubkey : Binary = from_json(&query(deps.as_ref(), env.clone(), QueryMsg::PubKey {}).unwrap()).unwrap();
+        assert_eq!(pubkey.len(), 33);
+        let owner : Addr = from_json(&query(deps.as_ref(), env.clone(), QueryMsg::Owner {}).unwrap()).unwrap();
+        assert_eq!(owner, info.sender);
+
+        let data = Binary::from(b"data");
+        let res = execute(deps.as_mut(), env.clone(), info, ExecuteMsg::SignData { data: data.clone() }).unwrap();
+        let signature = res.data.unwrap();
+
+        // the signature verifies outside of the contract as well
+        let verifying_key = VerifyingKey::from_sec1_bytes(&pubkey).unwrap();
+        verifying_key.verify(&data, &Signature::from_slice(&signature).unwrap()).unwrap();
+
+        let is_valid = |deps: Deps, data: &Binary, signature: &Binary| -> bool {
+            let res : ValidSignatureResponse = from_json(&query(deps, env.clone(), QueryMsg::ValidSignature { 
+                data: data.clone(), 
+                signature: signature.clone(), 
+                payload: None 
+            }).unwrap()).unwrap();
+            res.is_valid
+        };
+        assert!(is_valid(deps.as_ref(), &data, &signature));
+        assert!(!is_valid(deps.as_ref(), &Binary::from(b"other"), &signature));
+        assert!(!is_valid(deps.as_ref(), &data, &Binary::from(vec![1u8; 64])));
+        assert!(!is_valid(deps.as_ref(), &data, &Binary::from(b"malformed")));
+
+        let res : ValidSignaturesResponse = from_json(&query(deps.as_ref(), env.clone(), QueryMsg::ValidSignatures { 
+            data: vec![data.clone(), Binary::from(b"other")], 
+            signatures: vec![signature.clone(), signature.clone()], 
+            payload: None 
+        }).unwrap()).unwrap();
+        assert_eq!(res.are_valid, vec![true, false]);
+
+        let err = query(deps.as_ref(), env, QueryMsg::ValidSignatures { 
+            data: vec![data], 
+            signatures: vec![], 
+            payload: None 
+        });
+        assert!(err.is_err());
+    }
+
+
+    #[test]
+    fn key_generation_test() {
+        let env = mock_env();
+        let info = mock_info("creator", &[]);
+
+        let pubkey = |entropy: Option<Binary>| -> Binary {
+            let mut deps = mock_dependencies();
+            instantiate(deps.as_mut(), env.clone(), info.clone(), InstantiateMsg { owner: None, entropy }).unwrap();
+            from_json(&query(deps.as_ref(), env.clone(), QueryMsg::PubKey {}).unwrap()).unwrap()
+        };
+        assert_eq!(pubkey(None), pubkey(None));
+        assert_ne!(pubkey(None), pubkey(Some(Binary::from(b"entropy"))));
+
+        let mut other_block = env.clone();
+        other_block.block.random = Some(Binary::from(vec![7u8; 32]));
+        let mut deps = mock_dependencies();
+        instantiate(deps.as_mut(), other_block.clone(), info.clone(), InstantiateMsg { owner: None, entropy: None }).unwrap();
+        let other : Binary = from_json(&query(deps.as_ref(), env.clone(), QueryMsg::PubKey {}).unwrap()).unwrap();
+        assert_ne!(other, pubkey(None));
+
+        // the key isn't generated without randomness
+        let mut no_random = env;
+        no_random.block.random = None;
+        let mut deps = mock_dependencies();
+        let err = instantiate(deps.as_mut(), no_random, info, InstantiateMsg { owner: None, entropy: None });
+        assert!(err.is_err());
+    }
+
+
+    #[test]
+    fn owner_test() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let creator = mock_info("creator", &[]);
+        let owner = mock_info("owner", &[]);
+
+        instantiate(deps.as_mut(), env.clone(), creator.clone(), InstantiateMsg { 
+            owner: Some(owner.sender.to_string()), 
+            entropy: None 
+        }).unwrap();
+
+        let msg = ExecuteMsg::SignData { data: Binary::from(b"data") };
+        execute(deps.as_mut(), env.clone(), owner, msg.clone()).unwrap();
+
+        let err = execute(deps.as_mut(), env, creator, msg);
+        assert!(err.is_err());
+    }
+}
@@ -1,154 +1,480 @@
 use types::wasm::{
-    entry_point, to_json_binary, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult
+    entry_point, to_json_binary, to_json_string, BankMsg, Binary, Bound, Coin, CosmosMsg, Deps, DepsMut, Env, MessageInfo,
+    Order, OverflowError, Response, StdResult, Storage, Uint256, WasmMsg
 };
 use cw82::{ValidSignaturesResponse, ValidSignatureResponse, CanExecuteResponse};
-use crate::{msg::{QueryMsg, InstantiateMsg, ExecuteMsg, SignedMsg}, state::PUBKEY};
+use cw81::{Secp256k1Verifier, SignatureVerifier};
+use crate::{
+    error::ContractError,
+    msg::{
+        register_interfaces, AllowedMsg, QueryMsg, InstantiateMsg, ExecuteMsg, MsgSignDoc, SessionKeyInfo, SessionKeysResponse, 
+        SignedMsg, SpendingLimit, SpendingLimitInfo, SpendingLimitsResponse
+    }, 
+    state::{SessionKey, NONCE, OWNER, PUBKEY, SESSION_KEYS, SPENDINGS, SPENDING_LIMITS}
+};
 
 pub const CONTRACT_NAME: &str = "crates:cw82-key-account";
 pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
-use sha2::{
-    Sha256, 
-    digest::{Update, Digest}
-};
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+/// How far a signed message may skip ahead of the stored nonce
+pub const MAX_NONCE_GAP: u64 = 1_000;
 
 #[entry_point]
 pub fn instantiate(deps: DepsMut, _ : Env, _ : MessageInfo, msg : InstantiateMsg,) 
--> StdResult<Response> {
+-> Result<Response, ContractError> {
     //cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
-    cw22::set_contract_supported_interface(
-        deps.storage, 
-        &[
-            cw22::ContractSupportedInterface {
-                supported_interface: cw82::INTERFACE_NAME.into(),
-                version: CONTRACT_VERSION.into()
-            },
-            cw22::ContractSupportedInterface {
-                supported_interface: "crates:cw81".into(),
-                version: CONTRACT_VERSION.into()
-            },
-            cw22::ContractSupportedInterface {
-                supported_interface: "crates:cw1".into(),
-                version: "1.1.1".into()
-            },
-            cw22::ContractSupportedInterface {
-                supported_interface: "crates:cw22".into(),
-                // TODO change version
-                version: CONTRACT_VERSION.into()
-            }
-        ]
-    )?;
+    register_interfaces(deps.storage)?;
+    if let Some(owner) = msg.owner {
+        OWNER.save(deps.storage, &deps.api.addr_validate(&owner)?)?;
+    }
+    for limit in msg.spending_limits.unwrap_or_default() {
+        if limit.period == 0 {
+            return Err(ContractError::ZeroPeriod {});
+        }
+        SPENDING_LIMITS.save(deps.storage, &limit.limit.denom, &limit)?;
+    }
     PUBKEY.save(deps.storage, &msg.pub_key)?;
+    NONCE.save(deps.storage, &0)?;
     Ok(Response::default())
 }
 
 
 #[entry_point]
-pub fn execute(deps: DepsMut, _ : Env, _ : MessageInfo, msg : ExecuteMsg) 
--> StdResult<Response> {
+pub fn execute(deps: DepsMut, env : Env, info : MessageInfo, msg : ExecuteMsg) 
+-> Result<Response, ContractError> {
     match msg {
         ExecuteMsg::Execute { msgs } => {
-            let key: Binary = PUBKEY.load(deps.storage)?;
-            let msgs : StdResult<Vec<CosmosMsg>, > = msgs
-                .iter()
-                .map(|msg| 
-                    validate_signed(deps.as_ref(), msg, &key)
-                )
-                .collect();
+            let mut nonce = NONCE.load(deps.storage)?;
+            let mut out = Vec::with_capacity(msgs.len());
+            let mut spending = vec![];
+
+            for (index, msg) in msgs.iter().enumerate() {
+                let validated = validate_msg(deps.as_ref(), &env, info.sender.as_str(), index, msg, nonce)?;
+                if let Some((pubkey, session)) = validated.session {
+                    SESSION_KEYS.save(deps.storage, &pubkey, &session)?;
+                }
+                // every signed message must use a higher nonce than the previous one
+                if let Some(used) = validated.nonce {
+                    nonce = used.checked_add(1).ok_or(ContractError::NonceTooHigh { 
+                        index, nonce: used, max: u64::MAX - 1 
+                    })?;
+                }
+                check_spending(deps.as_ref(), &env, index, &validated.msg, &mut spending)?;
+                out.push(validated.msg);
+            }
+            NONCE.save(deps.storage, &nonce)?;
+            record_spending(deps.storage, &env, &spending)?;
+
             Ok(Response::new()
-                .add_messages(msgs.unwrap())
+                .add_messages(out)
             )
-        }
+        },
+
+        ExecuteMsg::AddSessionKey { pubkey, expiration, allowed_msgs } => {
+            if !is_controller(deps.as_ref(), &env, &info)? {
+                return Err(ContractError::Unauthorized {});
+            }
+            if expiration.is_expired(&env.block) {
+                return Err(ContractError::SessionKeyExpired {});
+            }
+            SESSION_KEYS.save(deps.storage, &pubkey, &SessionKey {
+                expiration,
+                allowed_msgs,
+                spent: vec![],
+            })?;
+            Ok(Response::new().add_attribute("action", "add_session_key"))
+        },
+
+        ExecuteMsg::RevokeSessionKey { pubkey } => {
+            if !is_controller(deps.as_ref(), &env, &info)? {
+                return Err(ContractError::Unauthorized {});
+            }
+            if !SESSION_KEYS.has(deps.storage, &pubkey) {
+                return Err(ContractError::UnknownSessionKey {});
+            }
+            SESSION_KEYS.remove(deps.storage, &pubkey);
+            Ok(Response::new().add_attribute("action", "revoke_session_key"))
+        },
+
+        ExecuteMsg::SetSpendingLimit { limit, period } => {
+            if !is_controller(deps.as_ref(), &env, &info)? {
+                return Err(ContractError::Unauthorized {});
+            }
+            if period == 0 {
+                return Err(ContractError::ZeroPeriod {});
+            }
+            let current = SPENDING_LIMITS.may_load(deps.storage, &limit.denom)?;
+            // spendings recorded for another window don't add up to the new one
+            let reset = current.as_ref().is_some_and(|current| current.period != period);
+
+            // the keys constrained by the limits can't lift them and a reset would free the allowance
+            if !can_loosen_limits(deps.as_ref(), &env, &info)? {
+                let tightened = match &current {
+                    Some(current) => limit.amount <= current.limit.amount && !reset,
+                    None => true,
+                };
+                if !tightened {
+                    return Err(ContractError::LimitLoosened { denom: limit.denom });
+                }
+            }
+            if reset {
+                SPENDINGS.prefix(&limit.denom).clear(deps.storage, None);
+            }
+            SPENDING_LIMITS.save(deps.storage, &limit.denom, &SpendingLimit { limit: limit.clone(), period })?;
+            Ok(Response::new()
+                .add_attribute("action", "set_spending_limit")
+                .add_attribute("denom", limit.denom)
+            )
+        },
+
+        ExecuteMsg::RemoveSpendingLimit { denom } => {
+            if !can_loosen_limits(deps.as_ref(), &env, &info)? {
+                return Err(ContractError::LimitLoosened { denom });
+            }
+            SPENDING_LIMITS.remove(deps.storage, &denom);
+            SPENDINGS.prefix(&denom).clear(deps.storage, None);
+            Ok(Response::new()
+                .add_attribute("action", "remove_spending_limit")
+                .add_attribute("denom", denom)
+            )
+        },
     }
 }
 
 
 #[entry_point]
-pub fn query(deps: Deps, _: Env, msg: QueryMsg) -> StdResult<Binary> {
-    match msg {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<Binary, ContractError> {
+    let res = match msg {
         QueryMsg::PubKey {} => to_json_binary(&PUBKEY.load(deps.storage)?),
 
-        QueryMsg::CanExecute { msg, .. } => {
-            let key: Binary = PUBKEY.load(deps.storage)?;
-            let can_execute = 
-                if let Ok(_) = validate_signed(deps, &msg, &key) {
-                    true
-                } else {
-                    false
-                };
+        QueryMsg::Owner {} => to_json_binary(&OWNER.may_load(deps.storage)?),
+
+        QueryMsg::Nonce {} => to_json_binary(&NONCE.load(deps.storage)?),
+
+        QueryMsg::SessionKeys { start_after, limit } => {
+            let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+            let start = start_after.as_deref().map(Bound::exclusive);
+
+            let keys = SESSION_KEYS
+                .range(deps.storage, start, None, Order::Ascending)
+                .take(limit)
+                .map(|item| item.map(|(pubkey, key)| SessionKeyInfo {
+                    pubkey: pubkey.into(),
+                    expiration: key.expiration,
+                    allowed_msgs: key.allowed_msgs,
+                    spent: key.spent,
+                }))
+                .collect::<StdResult<Vec<_>>>()?;
+
+            to_json_binary(&SessionKeysResponse { keys })
+        },
+
+        QueryMsg::SpendingLimits { start_after, limit } => {
+            let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+            let start = start_after.as_deref().map(Bound::exclusive);
+
+            let limits = SPENDING_LIMITS
+                .range(deps.storage, start, None, Order::Ascending)
+                .take(limit)
+                .map(|item| {
+                    let (denom, limit) = item?;
+                    Ok(SpendingLimitInfo {
+                        remaining: Coin { denom, amount: remaining_allowance(deps.storage, &env, &limit)? },
+                        limit: limit.limit,
+                        period: limit.period,
+                    })
+                })
+                .collect::<StdResult<Vec<_>>>()?;
+
+            to_json_binary(&SpendingLimitsResponse { limits })
+        },
+
+        QueryMsg::RemainingAllowance { denom } => {
+            let remaining = match SPENDING_LIMITS.may_load(deps.storage, &denom)? {
+                Some(limit) => Some(Coin { amount: remaining_allowance(deps.storage, &env, &limit)?, denom }),
+                None => None,
+            };
+            to_json_binary(&remaining)
+        },
+
+        QueryMsg::SupportedInterface { name } => {
+            to_json_binary(&cw22::query_supported_interface(deps.storage, &name)?)
+        },
+
+        QueryMsg::SupportedInterfaces { start_after, limit } => {
+            to_json_binary(&cw22::query_supported_interfaces(deps.storage, start_after, limit)?)
+        },
+
+        QueryMsg::Supports { name, version_req } => {
+            to_json_binary(&cw22::query_supports(deps.storage, &name, &version_req)?)
+        },
+
+        QueryMsg::CanExecute { sender, msg } => {
+            let nonce = NONCE.load(deps.storage)?;
+            let can_execute = validate_msg(deps, &env, &sender, 0, &msg, nonce)
+                .and_then(|validated| check_spending(deps, &env, 0, &validated.msg, &mut vec![]))
+                .is_ok();
             to_json_binary(&CanExecuteResponse { can_execute })
         },
 
 
         QueryMsg::ValidSignature { signature, data, .. } => {
-            let hash = Sha256::new().chain(&data).finalize();
             let pk: Binary = PUBKEY.load(deps.storage)?;
 
             to_json_binary(&ValidSignatureResponse {
-                is_valid: deps.api.secp256k1_verify(
-                    &hash, 
-                    &signature, 
-                    &pk
-                ).unwrap_or(false),
+                is_valid: Secp256k1Verifier.is_valid(deps.api, &data, &signature, &pk),
             })
         },
 
         QueryMsg::ValidSignatures { signatures, data, .. } => {
-            let pk: Binary = PUBKEY.load(deps.storage).unwrap();
-
-            let are_valid : Vec<bool> = signatures
-                .iter()
-                .enumerate()
-                .map(|(i, signature)| {
-
-                    let hash = Sha256::new().chain(
-                        data.get(i).unwrap()
-                    ).finalize();
-
-                    deps.api.secp256k1_verify(
-                        &hash, 
-                        &signature, 
-                        &pk
-                    ).unwrap_or(false)
-
-                })
-                .collect(); 
+            if data.len() != signatures.len() {
+                return Err(ContractError::LengthMismatch { data: data.len(), signatures: signatures.len() });
+            }
+            let pk: Binary = PUBKEY.load(deps.storage)?;
 
             to_json_binary(&ValidSignaturesResponse {
-                are_valid
+                are_valid: Secp256k1Verifier.verify_batch(deps.api, &data, &signatures, &pk)?
             })
-
         }
-    }
+    }?;
+    Ok(res)
 }
 
 
-fn validate_signed(
+struct Validated {
+    msg: CosmosMsg,
+    /// Nonce of a signed message
+    nonce: Option<u64>,
+    /// Session key that signed the message with the updated spendings
+    session: Option<(Binary, SessionKey)>,
+}
+
+
+/// Checks the signature of `MsgSignDoc` made by the main key or by a session key that is allowed to sign the message.
+/// Other messages are only passed through when sent by the owner. The index of the message is used for the errors
+fn validate_msg(
     deps: Deps,
+    env: &Env,
+    sender: &str,
+    index: usize,
     msg: &cosmwasm_std::CosmosMsg<SignedMsg>,
-    key: &[u8],
-) -> StdResult<CosmosMsg> {
+    min_nonce: u64,
+) -> Result<Validated, ContractError> {
 
 
     match msg {
         cosmwasm_std::CosmosMsg::Custom(msg) => {
-            let hash = Sha256::new()
-                .chain(&to_json_binary(&msg.msg)?)
-                .finalize();
-            
-            deps.api.secp256k1_verify(
-                &hash, 
-                &msg.signed_hash, 
-                key
-            )?;
+            if msg.nonce < min_nonce {
+                return Err(ContractError::StaleNonce { index, nonce: msg.nonce, expected: min_nonce });
+            }
+            // without a cap a single signature could push the nonce to the end of its range
+            let max = min_nonce.saturating_add(MAX_NONCE_GAP).min(u64::MAX - 1);
+            if msg.nonce > max {
+                return Err(ContractError::NonceTooHigh { index, nonce: msg.nonce, max });
+            }
+            let session = match &msg.session_key {
+                Some(pubkey) => {
+                    let mut session = SESSION_KEYS
+                        .may_load(deps.storage, pubkey)?
+                        .ok_or(ContractError::UnknownSessionKey {})?;
+                    if session.expiration.is_expired(&env.block) {
+                        return Err(ContractError::SessionKeyExpired {});
+                    }
+                    check_allowed(index, env.contract.address.as_str(), &msg.msg, &mut session)?;
+                    Some((pubkey.clone(), session))
+                },
+                None => None,
+            };
+            let key = match &session {
+                Some((pubkey, _)) => pubkey.clone(),
+                None => PUBKEY.load(deps.storage)?,
+            };
 
-            Ok(msg.msg.clone())
+            let data = to_json_binary(&MsgSignDoc {
+                chain_id: env.block.chain_id.clone(),
+                account: env.contract.address.to_string(),
+                nonce: msg.nonce,
+                msg: msg.msg.clone(),
+            })?;
+            if !Secp256k1Verifier.is_valid(deps.api, &data, &msg.signed_hash, &key) {
+                return Err(ContractError::InvalidSignature { index });
+            }
+            Ok(Validated { msg: msg.msg.clone(), nonce: Some(msg.nonce), session })
         },
 
-        _ => Err(StdError::msg("Only SignedMsg is supported"))
+        other if is_owner(deps, sender)? => Ok(Validated {
+            msg: other.clone().change_custom().ok_or(ContractError::UnsupportedMessage { kind: message_kind(other) })?,
+            nonce: None,
+            session: None,
+        }),
+
+        other => Err(ContractError::UnsupportedMessage { kind: message_kind(other) })
         
     }
 
     
 
-}
\ No newline at end of file
+}
+
+
+fn is_owner(deps: Deps, sender: &str) -> StdResult<bool> {
+    Ok(OWNER.may_load(deps.storage)?.is_some_and(|owner| owner.as_str() == sender))
+}
+
+
+/// The account itself or its owner. Spending limits can only be raised or removed by `can_loosen_limits`
+fn is_controller(deps: Deps, env: &Env, info: &MessageInfo) -> StdResult<bool> {
+    Ok(info.sender == env.contract.address || is_owner(deps, info.sender.as_str())?)
+}
+
+
+/// The owner or the account itself when it has no owner. Otherwise nobody could ever raise the limits
+fn can_loosen_limits(deps: Deps, env: &Env, info: &MessageInfo) -> StdResult<bool> {
+    Ok(match OWNER.may_load(deps.storage)? {
+        Some(owner) => info.sender == owner,
+        None => info.sender == env.contract.address,
+    })
+}
+
+
+/// Checks the message against the scopes of the session key and adds the sent coins to the spendings.
+/// Executions of the account itself are never allowed so that session keys can't manage the keys or the limits
+fn check_allowed(index: usize, account: &str, msg: &CosmosMsg, session: &mut SessionKey) -> Result<(), ContractError> {
+    match msg {
+        CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, funds, .. }) => {
+            let allowed = funds.is_empty() && contract_addr != account && session.allowed_msgs.iter().any(|allowed| matches!(
+                allowed, AllowedMsg::WasmExecute { contracts } if contracts.contains(contract_addr)
+            ));
+            if !allowed {
+                return Err(ContractError::NotAllowed { index });
+            }
+        },
+
+        CosmosMsg::Bank(BankMsg::Send { amount, .. }) => {
+            let limit = session.allowed_msgs.iter().find_map(|allowed| match allowed {
+                AllowedMsg::BankSend { limit } => Some(limit),
+                _ => None,
+            }).ok_or(ContractError::NotAllowed { index })?;
+
+            for coin in amount {
+                let exceeded = || ContractError::SessionLimitExceeded { index, denom: coin.denom.clone() };
+                let max = limit.iter().find(|c| c.denom == coin.denom).ok_or_else(exceeded)?.amount;
+
+                if add_coin(&mut session.spent, coin).map_err(|_| exceeded())? > max {
+                    return Err(exceeded());
+                }
+            }
+        },
+
+        _ => return Err(ContractError::NotAllowed { index }),
+    }
+    Ok(())
+}
+
+
+/// Coins that leave the account with the message. `None` for messages whose effect can't be inspected,
+/// including the variants behind the features of cosmwasm-std that other crates of a build might enable,
+/// e.g. `Stargate` or `Any` that might encode a bank send
+fn outgoing_funds(msg: &CosmosMsg) -> Option<Vec<Coin>> {
+    let funds = match msg {
+        CosmosMsg::Bank(BankMsg::Send { amount, .. }) => amount.clone(),
+        CosmosMsg::Bank(BankMsg::Burn { amount }) => amount.clone(),
+        CosmosMsg::Wasm(WasmMsg::Execute { funds, .. }) => funds.clone(),
+        CosmosMsg::Wasm(WasmMsg::Instantiate { funds, .. }) => funds.clone(),
+        CosmosMsg::Wasm(WasmMsg::Migrate { .. } | WasmMsg::UpdateAdmin { .. } | WasmMsg::ClearAdmin { .. }) => vec![],
+        _ => return None,
+    };
+    Some(funds)
+}
+
+
+/// Checks the funds of the message against the spending limits taking into account the ones sent
+/// by the previous messages of the batch in `spending`. The funds are added to it afterwards
+fn check_spending(
+    deps: Deps, 
+    env: &Env, 
+    index: usize, 
+    msg: &CosmosMsg, 
+    spending: &mut Vec<Coin>
+) -> Result<(), ContractError> {
+    let funds = match outgoing_funds(msg) {
+        Some(funds) => funds,
+        None if SPENDING_LIMITS.is_empty(deps.storage) => return Ok(()),
+        None => return Err(ContractError::UncheckedMessage { index }),
+    };
+    for coin in funds {
+        let limit = match SPENDING_LIMITS.may_load(deps.storage, &coin.denom)? {
+            Some(limit) => limit,
+            None => continue,
+        };
+        let exceeded = || ContractError::SpendingLimitExceeded { index, denom: coin.denom.clone() };
+        if add_coin(spending, &coin).map_err(|_| exceeded())? > remaining_allowance(deps.storage, env, &limit)? {
+            return Err(exceeded());
+        }
+    }
+    Ok(())
+}
+
+
+/// Saves the spending at the current block time and prunes the entries that left the window
+fn record_spending(storage: &mut dyn Storage, env: &Env, spending: &[Coin]) -> StdResult<()> {
+    let now = env.block.time.seconds();
+    for coin in spending {
+        let limit = match SPENDING_LIMITS.may_load(storage, &coin.denom)? {
+            Some(limit) => limit,
+            None => continue,
+        };
+        let since = now.saturating_sub(limit.period);
+        let expired = SPENDINGS
+            .prefix(&coin.denom)
+            .keys(storage, None, Some(Bound::inclusive(since)), Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?;
+        for time in expired {
+            SPENDINGS.remove(storage, (&coin.denom, time));
+        }
+        SPENDINGS.update(storage, (&coin.denom, now), |spent| -> StdResult<_> {
+            Ok(spent.unwrap_or_default().checked_add(coin.amount)?)
+        })?;
+    }
+    Ok(())
+}
+
+
+/// Limit minus the amount sent during the last `period` seconds
+fn remaining_allowance(storage: &dyn Storage, env: &Env, limit: &SpendingLimit) -> StdResult<Uint256> {
+    let since = env.block.time.seconds().saturating_sub(limit.period);
+    let spent = SPENDINGS
+        .prefix(&limit.limit.denom)
+        .range(storage, Some(Bound::exclusive(since)), None, Order::Ascending)
+        .try_fold(Uint256::zero(), |total, item| -> StdResult<_> {
+            Ok(total.checked_add(item?.1)?)
+        })?;
+    Ok(limit.limit.amount.saturating_sub(spent))
+}
+
+
+/// Adds the coin to the list and returns the new total of its denom
+fn add_coin(coins: &mut Vec<Coin>, coin: &Coin) -> Result<Uint256, OverflowError> {
+    match coins.iter_mut().find(|c| c.denom == coin.denom) {
+        Some(existing) => {
+            existing.amount = existing.amount.checked_add(coin.amount)?;
+            Ok(existing.amount)
+        },
+        None => {
+            coins.push(coin.clone());
+            Ok(coin.amount)
+        }
+    }
+}
+
+
+/// Name of the variant of a message as it appears in JSON, e.g. "bank" or "wasm". Read from the serialized
+/// message since the variants available depend on the features of cosmwasm-std enabled in the build
+fn message_kind(msg: &cosmwasm_std::CosmosMsg<SignedMsg>) -> String {
+    to_json_string(msg)
+        .ok()
+        .and_then(|json| json.split('"').nth(1).map(str::to_string))
+        .unwrap_or_else(|| "other".into())
+}
@@ -0,0 +1,81 @@
+use thiserror::Error;
+use types::wasm::StdError;
+
+
+#[derive(Error, Debug)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Interface(#[from] cw22::Cw22Error),
+
+    #[error("Signature of the message at index {index} is invalid")]
+    InvalidSignature {
+        index: usize,
+    },
+
+    #[error("Nonce {nonce} of the message at index {index} has already been used, expected at least {expected}")]
+    StaleNonce {
+        index: usize,
+        nonce: u64,
+        expected: u64,
+    },
+
+    #[error("Nonce {nonce} of the message at index {index} is too far ahead, expected at most {max}")]
+    NonceTooHigh {
+        index: usize,
+        nonce: u64,
+        max: u64,
+    },
+
+    #[error("Got {signatures} signatures for {data} data entries")]
+    LengthMismatch {
+        data: usize,
+        signatures: usize,
+    },
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Session key is not registered")]
+    UnknownSessionKey {},
+
+    #[error("Session key has expired")]
+    SessionKeyExpired {},
+
+    #[error("Session key isn't allowed to sign the message at index {index}")]
+    NotAllowed {
+        index: usize,
+    },
+
+    #[error("Message at index {index} exceeds the limit of the session key for `{denom}`")]
+    SessionLimitExceeded {
+        index: usize,
+        denom: String,
+    },
+
+    #[error("Message at index {index} exceeds the spending limit for `{denom}`")]
+    SpendingLimitExceeded {
+        index: usize,
+        denom: String,
+    },
+
+    #[error("Period of a spending limit must be positive")]
+    ZeroPeriod {},
+
+    #[error("Only the owner can raise, remove or change the period of the spending limit of `{denom}`")]
+    LimitLoosened {
+        denom: String,
+    },
+
+    #[error("Message at index {index} can't be checked against the spending limits")]
+    UncheckedMessage {
+        index: usize,
+    },
+
+    #[error("Messages of kind `{kind}` must be wrapped in SignedMsg")]
+    UnsupportedMessage {
+        kind: String,
+    },
+}
@@ -1,4 +1,6 @@
 pub mod contract;
+pub mod error;
 pub mod msg;
 mod state;
+#[allow(clippy::module_inception)]
 mod tests;
\ No newline at end of file
@@ -1,17 +1,38 @@
-use types::wasm::{Binary, Empty, CosmosMsg};
+use types::wasm::{Addr, Binary, Coin, Empty, CosmosMsg};
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cw82::{account_query, account_execute};
+use cw22::{supported_interface_query, supports};
+use cw_utils::Expiration;
 
 #[cw_serde]
 pub struct InstantiateMsg {
-    pub pub_key: Binary
+    pub pub_key: Binary,
+    /// Address that can execute native messages and manage session keys without signatures
+    pub owner: Option<String>,
+    /// Limits applied to all the funds sent out from the account
+    pub spending_limits: Option<Vec<SpendingLimit>>,
 }
 
 
 #[cw_serde]
 pub struct SignedMsg<T = Empty> {
     pub msg : CosmosMsg::<T>,
-    pub signed_hash : Binary
+    /// Signature of the JSON encoded `MsgSignDoc`
+    pub signed_hash : Binary,
+    /// Must not be lower than the one returned by the `Nonce` query
+    pub nonce : u64,
+    /// Public key of the session key that made the signature. Defaults to the main key
+    pub session_key : Option<Binary>,
+}
+
+
+/// Pre-image of the signature binding the message to the chain, the account and the nonce
+#[cw_serde]
+pub struct MsgSignDoc<T = Empty> {
+    pub chain_id : String,
+    pub account : String,
+    pub nonce : u64,
+    pub msg : CosmosMsg::<T>,
 }
 
 impl<T> From<SignedMsg<T>> for CosmosMsg::<SignedMsg<T>> {
@@ -20,15 +41,119 @@ impl<T> From<SignedMsg<T>> for CosmosMsg::<SignedMsg<T>> {
     }
 }
 
+/// Kind of messages a session key is allowed to sign
+#[cw_serde]
+pub enum AllowedMsg {
+    /// `WasmMsg::Execute` without funds to any of the contracts
+    WasmExecute {
+        contracts: Vec<String>,
+    },
+    /// `BankMsg::Send` until the total amount sent during the session reaches the limit
+    BankSend {
+        limit: Vec<Coin>,
+    },
+}
+
+
+/// Cap on the amount of a denom sent out during any window of `period` seconds
+#[cw_serde]
+pub struct SpendingLimit {
+    pub limit: Coin,
+    /// Length of the rolling window in seconds, e.g. 86400 for a daily limit
+    pub period: u64,
+}
+
+
+#[cw_serde]
+pub struct SpendingLimitInfo {
+    pub limit: Coin,
+    pub period: u64,
+    /// Amount that can still be sent in the current window
+    pub remaining: Coin,
+}
+
+
+#[cw_serde]
+pub struct SpendingLimitsResponse {
+    pub limits: Vec<SpendingLimitInfo>,
+}
+
+
+#[cw_serde]
+pub struct SessionKeyInfo {
+    pub pubkey: Binary,
+    pub expiration: Expiration,
+    pub allowed_msgs: Vec<AllowedMsg>,
+    /// Total amount sent with `BankMsg::Send` so far
+    pub spent: Vec<Coin>,
+}
+
+
+#[cw_serde]
+pub struct SessionKeysResponse {
+    pub keys: Vec<SessionKeyInfo>,
+}
+
+
+#[supports(cw82, cw81, cw1, cw22, multi)]
 #[account_query]
+#[supported_interface_query]
 #[cw_serde]
 #[derive(QueryResponses)]
 pub enum QueryMsg<T = SignedMsg> {
     #[returns(Binary)]
     PubKey {},
+
+    #[returns(Option<Addr>)]
+    Owner {},
+
+    #[returns(u64)]
+    Nonce {},
+
+    #[returns(SessionKeysResponse)]
+    SessionKeys {
+        start_after: Option<Binary>,
+        limit: Option<u32>,
+    },
+
+    #[returns(SpendingLimitsResponse)]
+    SpendingLimits {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    /// Amount of the denom that can still be sent in the current window. `None` when the denom isn't limited
+    #[returns(Option<Coin>)]
+    RemainingAllowance {
+        denom: String,
+    },
 }
 
 
+/// Apart from `Execute` the messages can only be sent by the owner or by the account to itself,
+/// i.e. be signed by the main key and wrapped in `WasmMsg::Execute` to the account
 #[account_execute]
 #[cw_serde]
-pub enum ExecuteMsg<T = SignedMsg> {}
\ No newline at end of file
+pub enum ExecuteMsg<T = SignedMsg> {
+    AddSessionKey {
+        pubkey: Binary,
+        expiration: Expiration,
+        allowed_msgs: Vec<AllowedMsg>,
+    },
+
+    RevokeSessionKey {
+        pubkey: Binary,
+    },
+
+    /// Adds or replaces the limit of the denom. The account itself can only add limits or lower
+    /// the amount and extend the period of the existing ones
+    SetSpendingLimit {
+        limit: Coin,
+        period: u64,
+    },
+
+    /// Can only be sent by the owner
+    RemoveSpendingLimit {
+        denom: String,
+    },
+}
\ No newline at end of file
@@ -1,3 +1,31 @@
-use types::wasm::{Item, Binary};
+use cosmwasm_schema::cw_serde;
+use cw_utils::Expiration;
+use types::wasm::{Addr, Item, Map, Binary, Coin, Uint256};
 
-pub static PUBKEY : Item<Binary> = Item::new("s");
\ No newline at end of file
+use crate::msg::{AllowedMsg, SpendingLimit};
+
+
+#[cw_serde]
+pub struct SessionKey {
+    pub expiration: Expiration,
+    pub allowed_msgs: Vec<AllowedMsg>,
+    pub spent: Vec<Coin>,
+}
+
+
+pub static PUBKEY : Item<Binary> = Item::new("s");
+
+/// Address allowed to execute messages without signing them
+pub static OWNER : Item<Addr> = Item::new("o");
+
+/// Lowest nonce that a signed message can use
+pub static NONCE : Item<u64> = Item::new("n");
+
+/// Session keys by their public key
+pub static SESSION_KEYS : Map<&[u8], SessionKey> = Map::new("k");
+
+/// Spending limits by their denom
+pub static SPENDING_LIMITS : Map<&str, SpendingLimit> = Map::new("l");
+
+/// Amounts sent out by the denom and the block time in seconds. Pruned once they leave the window of the limit
+pub static SPENDINGS : Map<(&str, u64), Uint256> = Map::new("w");
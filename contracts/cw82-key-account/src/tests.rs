@@ -6,9 +6,12 @@ mod tests {
             message_info, mock_dependencies, mock_env
         }
     };
-    use cosmwasm_std::{BankMsg, CosmosMsg, Binary, to_json_binary, coins};
+    use cosmwasm_std::{Addr, BankMsg, Coin, CosmosMsg, Binary, DistributionMsg, Env, IbcMsg, IbcTimeout, StakingMsg, WasmMsg, to_json_binary, coin, coins};
+    use cw_utils::Expiration;
 
     use cw82::{CanExecuteResponse, ValidSignatureResponse};
+    use cosmwasm_schema::schema_for;
+    use cw22::{KnownInterface, SupportedInterfaceResponse, SupportedInterfacesResponse, SupportsInterfaceResponse};
     use k256::{
         ecdsa::{
             signature::DigestSigner,
@@ -23,11 +26,41 @@ mod tests {
     };
 
     use crate::{
-        contract::{instantiate, query}, 
-        msg::{InstantiateMsg, SignedMsg, QueryMsg}
+        contract::{execute, instantiate, query, MAX_NONCE_GAP}, 
+        error::ContractError,
+        msg::{AllowedMsg, InstantiateMsg, MsgSignDoc, SessionKeysResponse, SignedMsg, SpendingLimit, SpendingLimitsResponse, QueryMsg, ExecuteMsg}
     };
 
 
+    fn sign(secret_key: &SigningKey, env: &Env, nonce: u64, msg: types::wasm::CosmosMsg) -> CosmosMsg<SignedMsg> {
+        sign_as(secret_key, env, nonce, msg, None)
+    }
+
+    fn sign_as(
+        secret_key: &SigningKey, 
+        env: &Env, 
+        nonce: u64, 
+        msg: types::wasm::CosmosMsg, 
+        session_key: Option<Binary>
+    ) -> CosmosMsg<SignedMsg> {
+        let doc = MsgSignDoc {
+            chain_id: env.block.chain_id.clone(),
+            account: env.contract.address.to_string(),
+            nonce,
+            msg: msg.clone(),
+        };
+        let signed_hash: Signature = secret_key.sign_digest(
+            Sha256::new().chain(to_json_binary(&doc).unwrap())
+        );
+        CosmosMsg::Custom(SignedMsg {
+            signed_hash: signed_hash.to_bytes().as_slice().into(),
+            nonce,
+            msg,
+            session_key,
+        })
+    }
+
+
     #[test]
     fn can_execute_test() {
 
@@ -40,7 +73,9 @@ mod tests {
 
         // user store public key
         instantiate(deps.as_mut(), env.clone(), info.clone(), InstantiateMsg {
-            pub_key: public_key.to_encoded_point(false).as_bytes().into()
+            pub_key: public_key.to_encoded_point(false).as_bytes().into(),
+            owner: None,
+            spending_limits: None,
         }).unwrap();
 
 
@@ -50,7 +85,7 @@ mod tests {
         };
 
 
-        let res : CanExecuteResponse = from_json(&query(deps.as_ref(), env.clone(), 
+        let res : CanExecuteResponse = from_json(query(deps.as_ref(), env.clone(), 
             QueryMsg::CanExecute { 
                 sender: "test".into(),
                 msg: bank.clone().into()
@@ -58,13 +93,8 @@ mod tests {
         ).unwrap()).unwrap();
 
         // only supporting signed messages
-        assert_eq!(res.can_execute, false);
-
+        assert!(!res.can_execute);
 
-        let signed_hash: Signature = secret_key.sign_digest(
-            Sha256::new()
-            .chain(&to_json_binary(&CosmosMsg::<SignedMsg>::Bank(bank.clone())).unwrap())
-        );
 
         let msg =  types::wasm::CosmosMsg::Bank(
             types::wasm::BankMsg::Send { 
@@ -72,21 +102,575 @@ mod tests {
                 amount: types::wasm::coins(1, "test")
             }
         );
+        let msg = sign(&secret_key, &env, 0, msg);
 
-        let msg : CosmosMsg<SignedMsg> = CosmosMsg::Custom(SignedMsg {
-            signed_hash: signed_hash.to_bytes().as_slice().into(),
-            msg
-        });
 
-
-        let res : CanExecuteResponse = from_json(&query(deps.as_ref(), env.clone(), 
+        let res : CanExecuteResponse = from_json(query(deps.as_ref(), env.clone(), 
             QueryMsg::CanExecute { 
                 sender: "test".into(),
                 msg
             }
         ).unwrap()).unwrap();
 
-        assert_eq!(res.can_execute, true);
+        assert!(res.can_execute);
+    }
+
+
+    #[test]
+    fn replay_test() {
+
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = message_info(&deps.api.addr_make("creator"), &[]);
+
+        let secret_key = SigningKey::random(&mut OsRng);
+        let public_key = VerifyingKey::from(&secret_key);
+
+        instantiate(deps.as_mut(), env.clone(), info.clone(), InstantiateMsg {
+            pub_key: public_key.to_encoded_point(false).as_bytes().into(),
+            owner: None,
+            spending_limits: None,
+        }).unwrap();
+
+        let bank : types::wasm::CosmosMsg = BankMsg::Send { 
+            to_address: "test".into(), 
+            amount: coins(1, "test") 
+        }.into();
+
+        let can_execute = |deps: types::wasm::Deps, msg: CosmosMsg<SignedMsg>| -> bool {
+            let res : CanExecuteResponse = from_json(query(deps, env.clone(), 
+                QueryMsg::CanExecute { sender: "test".into(), msg }
+            ).unwrap()).unwrap();
+            res.can_execute
+        };
+        let nonce = |deps: types::wasm::Deps| -> u64 {
+            from_json(query(deps, env.clone(), QueryMsg::Nonce {}).unwrap()).unwrap()
+        };
+
+        let signed = sign(&secret_key, &env, 0, bank.clone());
+        assert!(can_execute(deps.as_ref(), signed.clone()));
+
+        let res = execute(deps.as_mut(), env.clone(), info.clone(), ExecuteMsg::Execute { 
+            msgs: vec![signed.clone()] 
+        }).unwrap();
+        assert_eq!(res.messages.len(), 1);
+        assert_eq!(nonce(deps.as_ref()), 1);
+
+        // same message can't be replayed
+        assert!(!can_execute(deps.as_ref(), signed.clone()));
+
+        // signatures for other chains and accounts
+        let mut other_chain = env.clone();
+        other_chain.block.chain_id = "other-chain".into();
+        assert!(!can_execute(deps.as_ref(), sign(&secret_key, &other_chain, 1, bank.clone())));
+
+        let mut other_account = env.clone();
+        other_account.contract.address = deps.api.addr_make("other");
+        assert!(!can_execute(deps.as_ref(), sign(&secret_key, &other_account, 1, bank.clone())));
+
+        // nonces can be skipped but must increase within a batch
+        execute(deps.as_mut(), env.clone(), info.clone(), ExecuteMsg::Execute { 
+            msgs: vec![sign(&secret_key, &env, 1, bank.clone()), sign(&secret_key, &env, 5, bank.clone())] 
+        }).unwrap();
+        assert_eq!(nonce(deps.as_ref()), 6);
+
+        assert!(!can_execute(deps.as_ref(), sign(&secret_key, &env, 3, bank.clone())));
+        assert!(can_execute(deps.as_ref(), sign(&secret_key, &env, 6, bank.clone())));
+
+        // nonces can't jump to the end of the range
+        assert!(can_execute(deps.as_ref(), sign(&secret_key, &env, 6 + MAX_NONCE_GAP, bank.clone())));
+        assert!(!can_execute(deps.as_ref(), sign(&secret_key, &env, 7 + MAX_NONCE_GAP, bank.clone())));
+        assert!(!can_execute(deps.as_ref(), sign(&secret_key, &env, u64::MAX, bank.clone())));
+
+        let err = execute(deps.as_mut(), env.clone(), info.clone(), ExecuteMsg::Execute { 
+            msgs: vec![sign(&secret_key, &env, 7, bank.clone()), sign(&secret_key, &env, 7, bank.clone())] 
+        });
+        assert!(err.is_err());
+        assert_eq!(nonce(deps.as_ref()), 6);
+
+        let err = execute(deps.as_mut(), env.clone(), info, ExecuteMsg::Execute { msgs: vec![signed] });
+        assert!(err.is_err());
+    }
+
+
+    #[test]
+    fn errors_test() {
+
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = message_info(&deps.api.addr_make("creator"), &[]);
+
+        let secret_key = SigningKey::random(&mut OsRng);
+        let another_key = SigningKey::random(&mut OsRng);
+        let public_key = VerifyingKey::from(&secret_key);
+
+        instantiate(deps.as_mut(), env.clone(), info.clone(), InstantiateMsg {
+            pub_key: public_key.to_encoded_point(false).as_bytes().into(),
+            owner: None,
+            spending_limits: None,
+        }).unwrap();
+
+        let bank : types::wasm::CosmosMsg = BankMsg::Send { 
+            to_address: "test".into(), 
+            amount: coins(1, "test") 
+        }.into();
+
+        let err = query(deps.as_ref(), env.clone(), QueryMsg::ValidSignatures { 
+            data: vec![Binary::default(), Binary::default()], 
+            signatures: vec![Binary::default()], 
+            payload: None 
+        }).unwrap_err();
+        assert!(matches!(err, ContractError::LengthMismatch { data: 2, signatures: 1 }));
+
+        let err = execute(deps.as_mut(), env.clone(), info.clone(), ExecuteMsg::Execute { 
+            msgs: vec![BankMsg::Send { to_address: "test".into(), amount: coins(1, "test") }.into()] 
+        }).unwrap_err();
+        assert!(matches!(err, ContractError::UnsupportedMessage { kind } if kind == "bank"));
+
+        let err = execute(deps.as_mut(), env.clone(), info.clone(), ExecuteMsg::Execute { 
+            msgs: vec![sign(&secret_key, &env, 0, bank.clone()), sign(&another_key, &env, 1, bank.clone())] 
+        }).unwrap_err();
+        assert!(matches!(err, ContractError::InvalidSignature { index: 1 }));
+
+        // malformed signature isn't a trap either
+        let err = execute(deps.as_mut(), env.clone(), info.clone(), ExecuteMsg::Execute { 
+            msgs: vec![CosmosMsg::Custom(SignedMsg { msg: bank.clone(), signed_hash: Binary::default(), nonce: 0, session_key: None })] 
+        }).unwrap_err();
+        assert!(matches!(err, ContractError::InvalidSignature { index: 0 }));
+
+        execute(deps.as_mut(), env.clone(), info.clone(), ExecuteMsg::Execute { 
+            msgs: vec![sign(&secret_key, &env, 3, bank.clone())] 
+        }).unwrap();
+        let err = execute(deps.as_mut(), env.clone(), info, ExecuteMsg::Execute { 
+            msgs: vec![sign(&secret_key, &env, 2, bank)] 
+        }).unwrap_err();
+        assert!(matches!(err, ContractError::StaleNonce { index: 0, nonce: 2, expected: 4 }));
+    }
+
+
+    #[test]
+    fn session_key_test() {
+
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+        let info = message_info(&deps.api.addr_make("creator"), &[]);
+        let account = message_info(&env.contract.address, &[]);
+
+        let secret_key = SigningKey::random(&mut OsRng);
+        let public_key = VerifyingKey::from(&secret_key);
+
+        let session_secret = SigningKey::random(&mut OsRng);
+        let session_key : Binary = VerifyingKey::from(&session_secret).to_encoded_point(true).as_bytes().into();
+
+        instantiate(deps.as_mut(), env.clone(), info.clone(), InstantiateMsg {
+            pub_key: public_key.to_encoded_point(false).as_bytes().into(),
+            owner: None,
+            spending_limits: None,
+        }).unwrap();
+
+        let target = deps.api.addr_make("target").to_string();
+        let add = ExecuteMsg::AddSessionKey { 
+            pubkey: session_key.clone(), 
+            expiration: Expiration::AtHeight(env.block.height + 100), 
+            allowed_msgs: vec![
+                AllowedMsg::WasmExecute { contracts: vec![target.clone(), env.contract.address.to_string()] },
+                AllowedMsg::BankSend { limit: coins(100, "uatom") },
+            ] 
+        };
+
+        // main key signs a message of the account to itself
+        let res = execute(deps.as_mut(), env.clone(), info.clone(), ExecuteMsg::Execute { 
+            msgs: vec![sign(&secret_key, &env, 0, WasmMsg::Execute { 
+                contract_addr: env.contract.address.to_string(), 
+                msg: to_json_binary(&add).unwrap(), 
+                funds: vec![] 
+            }.into())] 
+        }).unwrap();
+        assert_eq!(res.messages.len(), 1);
+        execute(deps.as_mut(), env.clone(), account.clone(), add.clone()).unwrap();
+
+        let can_execute = |deps: types::wasm::Deps, env: &Env, nonce: u64, msg: types::wasm::CosmosMsg| -> bool {
+            let res : CanExecuteResponse = from_json(query(deps, env.clone(), QueryMsg::CanExecute { 
+                sender: "test".into(), 
+                msg: sign_as(&session_secret, env, nonce, msg, Some(session_key.clone()))
+            }).unwrap()).unwrap();
+            res.can_execute
+        };
+        let wasm_execute = |contract: &str, funds| -> types::wasm::CosmosMsg {
+            WasmMsg::Execute { contract_addr: contract.into(), msg: to_json_binary("{}").unwrap(), funds }.into()
+        };
+        let send = |amount, denom| -> types::wasm::CosmosMsg {
+            BankMsg::Send { to_address: "test".into(), amount: coins(amount, denom) }.into()
+        };
+
+        assert!(can_execute(deps.as_ref(), &env, 1, wasm_execute(&target, vec![])));
+        assert!(!can_execute(deps.as_ref(), &env, 1, wasm_execute(&target, coins(1, "uatom"))));
+        assert!(!can_execute(deps.as_ref(), &env, 1, wasm_execute("other", vec![])));
+        assert!(!can_execute(deps.as_ref(), &env, 1, send(101, "uatom")));
+        assert!(!can_execute(deps.as_ref(), &env, 1, send(1, "uosmo")));
+        assert!(!can_execute(deps.as_ref(), &env, 1, WasmMsg::ClearAdmin { contract_addr: target.clone() }.into()));
+        // the account itself is never in scope even when listed
+        assert!(!can_execute(deps.as_ref(), &env, 1, wasm_execute(env.contract.address.as_str(), vec![])));
+
+        // session key can't pretend to be the main key
+        let msg = sign_as(&session_secret, &env, 1, send(1, "uatom"), None);
+        let res : CanExecuteResponse = from_json(query(deps.as_ref(), env.clone(), QueryMsg::CanExecute { 
+            sender: "test".into(), 
+            msg
+        }).unwrap()).unwrap();
+        assert!(!res.can_execute);
+
+        execute(deps.as_mut(), env.clone(), info.clone(), ExecuteMsg::Execute { 
+            msgs: vec![
+                sign_as(&session_secret, &env, 1, send(60, "uatom"), Some(session_key.clone())),
+                sign_as(&session_secret, &env, 2, wasm_execute(&target, vec![]), Some(session_key.clone())),
+            ]
+        }).unwrap();
+
+        // the limit is shared by the whole session
+        assert!(!can_execute(deps.as_ref(), &env, 3, send(50, "uatom")));
+        assert!(can_execute(deps.as_ref(), &env, 3, send(40, "uatom")));
+
+        let keys : SessionKeysResponse = from_json(query(deps.as_ref(), env.clone(), QueryMsg::SessionKeys { 
+            start_after: None, 
+            limit: None 
+        }).unwrap()).unwrap();
+        assert_eq!(keys.keys.len(), 1);
+        assert_eq!(keys.keys[0].spent, coins(60, "uatom"));
+
+        let mut later = env.clone();
+        later.block.height += 100;
+        assert!(!can_execute(deps.as_ref(), &later, 3, send(40, "uatom")));
+
+        let revoke = ExecuteMsg::RevokeSessionKey { pubkey: session_key.clone() };
+        let err = execute(deps.as_mut(), env.clone(), info.clone(), revoke.clone()).unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+        execute(deps.as_mut(), env.clone(), account.clone(), revoke).unwrap();
+        assert!(!can_execute(deps.as_ref(), &env, 3, send(40, "uatom")));
+
+        let err = execute(deps.as_mut(), env.clone(), info, add).unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+
+        env.block.height += 1;
+        let err = execute(deps.as_mut(), env.clone(), account, ExecuteMsg::AddSessionKey { 
+            pubkey: session_key, 
+            expiration: Expiration::AtHeight(env.block.height), 
+            allowed_msgs: vec![] 
+        }).unwrap_err();
+        assert!(matches!(err, ContractError::SessionKeyExpired {}));
+    }
+
+
+    #[test]
+    fn owner_test() {
+
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let owner = message_info(&deps.api.addr_make("owner"), &[]);
+        let stranger = message_info(&deps.api.addr_make("stranger"), &[]);
+
+        let secret_key = SigningKey::random(&mut OsRng);
+        let public_key = VerifyingKey::from(&secret_key);
+
+        instantiate(deps.as_mut(), env.clone(), stranger.clone(), InstantiateMsg {
+            pub_key: public_key.to_encoded_point(false).as_bytes().into(),
+            owner: Some(owner.sender.to_string()),
+            spending_limits: None,
+        }).unwrap();
+
+        let stored : Option<Addr> = from_json(query(deps.as_ref(), env.clone(), QueryMsg::Owner {}).unwrap()).unwrap();
+        assert_eq!(stored, Some(owner.sender.clone()));
+
+        let bank = BankMsg::Send { to_address: "test".into(), amount: coins(1, "uatom") };
+        let can_execute = |deps: types::wasm::Deps, sender: &Addr, msg: CosmosMsg<SignedMsg>| -> bool {
+            let res : CanExecuteResponse = from_json(query(deps, env.clone(), QueryMsg::CanExecute { 
+                sender: sender.to_string(), 
+                msg
+            }).unwrap()).unwrap();
+            res.can_execute
+        };
+
+        assert!(can_execute(deps.as_ref(), &owner.sender, bank.clone().into()));
+        assert!(!can_execute(deps.as_ref(), &stranger.sender, bank.clone().into()));
+
+        // signed messages are accepted from anyone
+        let signed = sign(&secret_key, &env, 0, bank.clone().into());
+        assert!(can_execute(deps.as_ref(), &owner.sender, signed.clone()));
+        assert!(can_execute(deps.as_ref(), &stranger.sender, signed.clone()));
+
+        // native messages don't consume nonces
+        let res = execute(deps.as_mut(), env.clone(), owner.clone(), ExecuteMsg::Execute { 
+            msgs: vec![bank.clone().into(), signed.clone(), bank.clone().into()] 
+        }).unwrap();
+        assert_eq!(res.messages.len(), 3);
+        let nonce : u64 = from_json(query(deps.as_ref(), env.clone(), QueryMsg::Nonce {}).unwrap()).unwrap();
+        assert_eq!(nonce, 1);
+
+        // the owner manages session keys directly
+        let session_key : Binary = VerifyingKey::from(&SigningKey::random(&mut OsRng)).to_encoded_point(true).as_bytes().into();
+        execute(deps.as_mut(), env.clone(), owner.clone(), ExecuteMsg::AddSessionKey { 
+            pubkey: session_key.clone(), 
+            expiration: Expiration::Never {}, 
+            allowed_msgs: vec![] 
+        }).unwrap();
+        execute(deps.as_mut(), env.clone(), owner, ExecuteMsg::RevokeSessionKey { pubkey: session_key }).unwrap();
+
+        let err = execute(deps.as_mut(), env.clone(), stranger, ExecuteMsg::Execute { 
+            msgs: vec![bank.into()] 
+        }).unwrap_err();
+        assert!(matches!(err, ContractError::UnsupportedMessage { kind } if kind == "bank"));
+    }
+
+
+    #[test]
+    fn spending_limits_test() {
+
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+        let owner = message_info(&deps.api.addr_make("owner"), &[]);
+        let stranger = message_info(&deps.api.addr_make("stranger"), &[]);
+
+        let public_key = VerifyingKey::from(&SigningKey::random(&mut OsRng));
+
+        instantiate(deps.as_mut(), env.clone(), owner.clone(), InstantiateMsg {
+            pub_key: public_key.to_encoded_point(false).as_bytes().into(),
+            owner: Some(owner.sender.to_string()),
+            spending_limits: Some(vec![SpendingLimit { limit: coin(100, "uatom"), period: 86400 }]),
+        }).unwrap();
+
+        let send = |amount| -> CosmosMsg<SignedMsg> {
+            BankMsg::Send { to_address: "test".into(), amount: coins(amount, "uatom") }.into()
+        };
+        let remaining = |deps: types::wasm::Deps, env: &Env, denom: &str| -> Option<Coin> {
+            from_json(query(deps, env.clone(), QueryMsg::RemainingAllowance { denom: denom.into() }).unwrap()).unwrap()
+        };
+        let can_execute = |deps: types::wasm::Deps, env: &Env, msg: CosmosMsg<SignedMsg>| -> bool {
+            let res : CanExecuteResponse = from_json(query(deps, env.clone(), QueryMsg::CanExecute { 
+                sender: owner.sender.to_string(), 
+                msg
+            }).unwrap()).unwrap();
+            res.can_execute
+        };
+
+        execute(deps.as_mut(), env.clone(), owner.clone(), ExecuteMsg::Execute { msgs: vec![send(60)] }).unwrap();
+        assert_eq!(remaining(deps.as_ref(), &env, "uatom"), Some(coin(40, "uatom")));
+        assert_eq!(remaining(deps.as_ref(), &env, "uosmo"), None);
+
+        assert!(can_execute(deps.as_ref(), &env, send(40)));
+        assert!(!can_execute(deps.as_ref(), &env, send(41)));
+        assert!(!can_execute(deps.as_ref(), &env, WasmMsg::Execute { 
+            contract_addr: "contract".into(), 
+            msg: to_json_binary("{}").unwrap(), 
+            funds: coins(41, "uatom") 
+        }.into()));
+        assert!(!can_execute(deps.as_ref(), &env, WasmMsg::Instantiate { 
+            admin: None, 
+            code_id: 1, 
+            label: "label".into(), 
+            msg: to_json_binary("{}").unwrap(), 
+            funds: coins(41, "uatom") 
+        }.into()));
+        assert!(!can_execute(deps.as_ref(), &env, BankMsg::Burn { amount: coins(41, "uatom") }.into()));
+        assert!(can_execute(deps.as_ref(), &env, BankMsg::Send { 
+            to_address: "test".into(), 
+            amount: coins(1000, "uosmo") 
+        }.into()));
+
+        // messages of a batch are counted together
+        let err = execute(deps.as_mut(), env.clone(), owner.clone(), ExecuteMsg::Execute { 
+            msgs: vec![send(30), send(30)] 
+        }).unwrap_err();
+        assert!(matches!(err, ContractError::SpendingLimitExceeded { index: 1, denom } if denom == "uatom"));
+
+        // the window rolls with the block time
+        env.block.time = env.block.time.plus_seconds(43200);
+        execute(deps.as_mut(), env.clone(), owner.clone(), ExecuteMsg::Execute { msgs: vec![send(40)] }).unwrap();
+        assert!(!can_execute(deps.as_ref(), &env, send(1)));
+
+        env.block.time = env.block.time.plus_seconds(43200);
+        assert_eq!(remaining(deps.as_ref(), &env, "uatom"), Some(coin(60, "uatom")));
+        assert!(can_execute(deps.as_ref(), &env, send(60)));
+
+        execute(deps.as_mut(), env.clone(), owner.clone(), ExecuteMsg::SetSpendingLimit { 
+            limit: coin(10, "uosmo"), 
+            period: 3600 
+        }).unwrap();
+        let res : SpendingLimitsResponse = from_json(query(deps.as_ref(), env.clone(), QueryMsg::SpendingLimits { 
+            start_after: None, 
+            limit: None 
+        }).unwrap()).unwrap();
+        assert_eq!(res.limits.len(), 2);
+        assert_eq!(res.limits[0].remaining, coin(60, "uatom"));
+        assert_eq!(res.limits[1].remaining, coin(10, "uosmo"));
+
+        execute(deps.as_mut(), env.clone(), owner.clone(), ExecuteMsg::RemoveSpendingLimit { denom: "uatom".into() }).unwrap();
+        assert_eq!(remaining(deps.as_ref(), &env, "uatom"), None);
+        assert!(can_execute(deps.as_ref(), &env, send(1000)));
+
+        let err = execute(deps.as_mut(), env.clone(), owner, ExecuteMsg::SetSpendingLimit { 
+            limit: coin(10, "uatom"), 
+            period: 0 
+        }).unwrap_err();
+        assert!(matches!(err, ContractError::ZeroPeriod {}));
+
+        let err = execute(deps.as_mut(), env.clone(), stranger, ExecuteMsg::SetSpendingLimit { 
+            limit: coin(10, "uatom"), 
+            period: 3600 
+        }).unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+    }
+
+
+    #[test]
+    fn uninspected_messages_test() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let owner = message_info(&deps.api.addr_make("owner"), &[]);
+
+        instantiate(deps.as_mut(), env.clone(), owner.clone(), InstantiateMsg {
+            pub_key: VerifyingKey::from(&SigningKey::random(&mut OsRng)).to_encoded_point(false).as_bytes().into(),
+            owner: Some(owner.sender.to_string()),
+            spending_limits: None,
+        }).unwrap();
+
+        // encoded `MsgSend` moving the funds around the limits
+        let stargate = || -> CosmosMsg<SignedMsg> { CosmosMsg::Stargate {
+            type_url: "/cosmos.bank.v1beta1.MsgSend".into(),
+            value: Binary::from(b"encoded"),
+        }};
+        // variants behind the features of cosmwasm-std that the contract doesn't enable
+        let feature_gated : [CosmosMsg<SignedMsg>; 4] = [
+            IbcMsg::Transfer { 
+                channel_id: "channel-0".into(), 
+                to_address: "remote".into(), 
+                amount: coin(1, "uatom"), 
+                timeout: IbcTimeout::with_timestamp(env.block.time.plus_seconds(60)), 
+                memo: None 
+            }.into(),
+            WasmMsg::Instantiate2 { 
+                admin: None, 
+                code_id: 1, 
+                label: "label".into(), 
+                msg: to_json_binary("{}").unwrap(), 
+                funds: coins(1, "uatom"), 
+                salt: Binary::from(b"salt") 
+            }.into(),
+            StakingMsg::Delegate { validator: "validator".into(), amount: coin(1, "uatom") }.into(),
+            DistributionMsg::FundCommunityPool { amount: coins(1, "uatom") }.into(),
+        ];
+
+        // nothing to bypass without limits
+        execute(deps.as_mut(), env.clone(), owner.clone(), ExecuteMsg::Execute { msgs: vec![stargate()] }).unwrap();
+
+        execute(deps.as_mut(), env.clone(), owner.clone(), ExecuteMsg::SetSpendingLimit { 
+            limit: coin(100, "uatom"), 
+            period: 86400 
+        }).unwrap();
+
+        let err = execute(deps.as_mut(), env.clone(), owner.clone(), ExecuteMsg::Execute { 
+            msgs: vec![BankMsg::Send { to_address: "test".into(), amount: coins(1, "uatom") }.into(), stargate()] 
+        }).unwrap_err();
+        assert!(matches!(err, ContractError::UncheckedMessage { index: 1 }));
+
+        let res : CanExecuteResponse = from_json(query(deps.as_ref(), env.clone(), QueryMsg::CanExecute { 
+            sender: owner.sender.to_string(), 
+            msg: stargate()
+        }).unwrap()).unwrap();
+        assert!(!res.can_execute);
+
+        for msg in feature_gated {
+            let err = execute(deps.as_mut(), env.clone(), owner.clone(), ExecuteMsg::Execute { msgs: vec![msg] }).unwrap_err();
+            assert!(matches!(err, ContractError::UncheckedMessage { index: 0 }));
+        }
+
+        // messages known to move no funds are still fine
+        execute(deps.as_mut(), env.clone(), owner, ExecuteMsg::Execute { msgs: vec![
+            WasmMsg::ClearAdmin { contract_addr: "contract".into() }.into()
+        ] }).unwrap();
+    }
+
+
+    #[test]
+    fn loosening_limits_test() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let owner = message_info(&deps.api.addr_make("owner"), &[]);
+        let account = message_info(&env.contract.address, &[]);
+
+        instantiate(deps.as_mut(), env.clone(), owner.clone(), InstantiateMsg {
+            pub_key: VerifyingKey::from(&SigningKey::random(&mut OsRng)).to_encoded_point(false).as_bytes().into(),
+            owner: Some(owner.sender.to_string()),
+            spending_limits: Some(vec![SpendingLimit { limit: coin(100, "uatom"), period: 86400 }]),
+        }).unwrap();
+
+        let set = |amount: u128, period: u64| ExecuteMsg::SetSpendingLimit { limit: coin(amount, "uatom"), period };
+
+        // the main key signing a message to the account itself can't lift the limits
+        let err = execute(deps.as_mut(), env.clone(), account.clone(), ExecuteMsg::RemoveSpendingLimit { 
+            denom: "uatom".into() 
+        }).unwrap_err();
+        assert!(matches!(err, ContractError::LimitLoosened { denom } if denom == "uatom"));
+
+        for (amount, period) in [(101, 86400), (100, 3600), (1000, 864000)] {
+            let err = execute(deps.as_mut(), env.clone(), account.clone(), set(amount, period)).unwrap_err();
+            assert!(matches!(err, ContractError::LimitLoosened { .. }));
+        }
+
+        // but can tighten them without touching the period that would reset the spendings
+        let err = execute(deps.as_mut(), env.clone(), account.clone(), set(50, 86400 * 7)).unwrap_err();
+        assert!(matches!(err, ContractError::LimitLoosened { .. }));
+        execute(deps.as_mut(), env.clone(), account.clone(), set(50, 86400)).unwrap();
+        execute(deps.as_mut(), env.clone(), account.clone(), ExecuteMsg::SetSpendingLimit { 
+            limit: coin(10, "uosmo"), 
+            period: 3600 
+        }).unwrap();
+
+        // while the owner can do both
+        execute(deps.as_mut(), env.clone(), owner.clone(), set(1000, 3600)).unwrap();
+        execute(deps.as_mut(), env.clone(), owner, ExecuteMsg::RemoveSpendingLimit { denom: "uatom".into() }).unwrap();
+    }
+
+
+    #[test]
+    fn ownerless_limits_test() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = message_info(&deps.api.addr_make("creator"), &[]);
+        let account = message_info(&env.contract.address, &[]);
+        let secret_key = SigningKey::random(&mut OsRng);
+
+        instantiate(deps.as_mut(), env.clone(), info.clone(), InstantiateMsg {
+            pub_key: VerifyingKey::from(&secret_key).to_encoded_point(false).as_bytes().into(),
+            owner: None,
+            spending_limits: Some(vec![SpendingLimit { limit: coin(100, "uatom"), period: 86400 }]),
+        }).unwrap();
+
+        let remaining = |deps: types::wasm::Deps| -> Option<Coin> {
+            from_json(query(deps, env.clone(), QueryMsg::RemainingAllowance { denom: "uatom".into() }).unwrap()).unwrap()
+        };
+        let set = |amount: u128, period: u64| ExecuteMsg::SetSpendingLimit { limit: coin(amount, "uatom"), period };
+
+        execute(deps.as_mut(), env.clone(), info.clone(), ExecuteMsg::Execute { 
+            msgs: vec![sign(&secret_key, &env, 0, BankMsg::Send { to_address: "test".into(), amount: coins(60, "uatom") }.into())] 
+        }).unwrap();
+        assert_eq!(remaining(deps.as_ref()), Some(coin(40, "uatom")));
+
+        // other senders still can't touch the limits
+        let err = execute(deps.as_mut(), env.clone(), info.clone(), set(1000, 86400)).unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+        let err = execute(deps.as_mut(), env.clone(), info, ExecuteMsg::RemoveSpendingLimit { denom: "uatom".into() }).unwrap_err();
+        assert!(matches!(err, ContractError::LimitLoosened { .. }));
+
+        // without an owner the account itself can raise the limit
+        execute(deps.as_mut(), env.clone(), account.clone(), set(200, 86400)).unwrap();
+        assert_eq!(remaining(deps.as_ref()), Some(coin(140, "uatom")));
+
+        // a new period starts from scratch
+        execute(deps.as_mut(), env.clone(), account.clone(), set(200, 3600)).unwrap();
+        assert_eq!(remaining(deps.as_ref()), Some(coin(200, "uatom")));
+
+        execute(deps.as_mut(), env.clone(), account, ExecuteMsg::RemoveSpendingLimit { denom: "uatom".into() }).unwrap();
+        assert_eq!(remaining(deps.as_ref()), None);
     }
 
 
@@ -107,7 +691,9 @@ mod tests {
 
         // user store public key
         instantiate(deps.as_mut(), env.clone(), info.clone(), InstantiateMsg {
-            pub_key: public_key.to_encoded_point(false).as_bytes().into()
+            pub_key: public_key.to_encoded_point(false).as_bytes().into(),
+            owner: None,
+            spending_limits: None,
         }).unwrap();
 
 
@@ -128,7 +714,7 @@ mod tests {
         // dapp verifies signature from the contract
         let query_res = query(deps.as_ref(), env.clone(), query_msg.clone()).unwrap();
         let res : ValidSignatureResponse = from_json(&query_res).unwrap();
-        assert_eq!(res.is_valid, true);
+        assert!(res.is_valid);
 
 
         // if users has another key the signature is wrong
@@ -142,6 +728,81 @@ mod tests {
         };      
         let query_res = query(deps.as_ref(), env.clone(), another_msg).unwrap();
         let res : ValidSignatureResponse = from_json(&query_res).unwrap();
-        assert_eq!(res.is_valid, false);
+        assert!(!res.is_valid);
+    }
+
+
+    #[test]
+    fn supported_interfaces_test() {
+
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = message_info(&deps.api.addr_make("creator"), &[]);
+
+        let secret_key = SigningKey::random(&mut OsRng);
+        let public_key = VerifyingKey::from(&secret_key);
+
+        instantiate(deps.as_mut(), env.clone(), info.clone(), InstantiateMsg {
+            pub_key: public_key.to_encoded_point(false).as_bytes().into(),
+            owner: None,
+            spending_limits: None,
+        }).unwrap();
+
+
+        let res : SupportedInterfaceResponse = from_json(query(deps.as_ref(), env.clone(), 
+            QueryMsg::SupportedInterface { name: cw82::INTERFACE_NAME.into() }
+        ).unwrap()).unwrap();
+        assert_eq!(res.version, Some(env!("CARGO_PKG_VERSION").to_string()));
+
+
+        let res : SupportedInterfacesResponse = from_json(query(deps.as_ref(), env.clone(), 
+            QueryMsg::SupportedInterfaces { start_after: None, limit: None }
+        ).unwrap()).unwrap();
+        assert_eq!(res.interfaces.len(), 6);
+
+
+        let res : SupportsInterfaceResponse = from_json(query(deps.as_ref(), env.clone(), 
+            QueryMsg::Supports { name: "crates:cw1".into(), version_req: "^1.1".into() }
+        ).unwrap()).unwrap();
+        assert!(res.supported);
+
+
+        let res : SupportsInterfaceResponse = from_json(query(deps.as_ref(), env.clone(), 
+            QueryMsg::Supports { name: "crates:cw83".into(), version_req: "*".into() }
+        ).unwrap()).unwrap();
+        assert!(!res.supported);
+    }
+
+
+    #[test]
+    fn declared_interfaces_are_implemented() {
+
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = message_info(&deps.api.addr_make("creator"), &[]);
+
+        let secret_key = SigningKey::random(&mut OsRng);
+        let public_key = VerifyingKey::from(&secret_key);
+
+        instantiate(deps.as_mut(), env.clone(), info.clone(), InstantiateMsg {
+            pub_key: public_key.to_encoded_point(false).as_bytes().into(),
+            owner: None,
+            spending_limits: None,
+        }).unwrap();
+
+        let declared = cw22::query_supported_interfaces(&deps.storage, None, None)
+            .unwrap()
+            .interfaces;
+
+        for interface in [KnownInterface::Cw81, KnownInterface::Cw82, KnownInterface::Cw1] {
+            assert!(declared.iter().any(|d| interface.matches(&d.supported_interface)));
+        }
+
+        let missing = cw22::check_interface_variants(
+            &declared,
+            &schema_for!(QueryMsg),
+            Some(&schema_for!(ExecuteMsg)),
+        );
+        assert!(missing.is_empty(), "{missing:?}");
     }
 }
\ No newline at end of file
@@ -0,0 +1,37 @@
+use thiserror::Error;
+use types::wasm::StdError;
+
+
+#[derive(Error, Debug)]
+pub enum Cw22Error {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Version `{version}` of interface `{interface}` is invalid")]
+    InvalidVersion {
+        interface: String,
+        version: String,
+    },
+
+    #[error("Version requirement `{requirement}` is invalid")]
+    InvalidRequirement {
+        requirement: String,
+    },
+
+    #[error("Interface `{interface}` is not supported")]
+    NotSupported {
+        interface: String,
+    },
+
+    #[error("Interface `{interface}` is not known")]
+    UnknownInterface {
+        interface: String,
+    },
+
+    #[error("Version `{version}` of interface `{interface}` doesn't match the requirement `{requirement}`")]
+    VersionMismatch {
+        interface: String,
+        version: String,
+        requirement: String,
+    },
+}
@@ -0,0 +1,174 @@
+use std::{borrow::Cow, fmt, str::FromStr};
+use cosmwasm_schema::cw_serde;
+
+use crate::{ContractSupportedInterface, Cw22Error};
+
+
+/// Registry domain used in the canonical interface names
+pub const CANONICAL_PREFIX: &str = "crates:";
+/// Registry domains that are accepted as aliases of the canonical one
+pub const ALIAS_PREFIXES: [&str; 1] = ["crates.io:"];
+/// Suffix of the interface names with batch variants enabled by the `multi` feature
+pub const MULTI_SUFFIX: &str = "-multi";
+
+/// Version of cw1 the example contracts are compatible with
+pub const CW1_VERSION: &str = "1.1.1";
+/// All the cw-extra standards are released together and share the same version
+pub const CW_EXTRA_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+
+/// Canonical identifiers of the interfaces known to cw-extra
+#[cw_serde]
+#[derive(Copy, Eq, Hash)]
+pub enum KnownInterface {
+    Cw1,
+    Cw22,
+    Cw81,
+    Cw81Multi,
+    Cw82,
+    Cw82Multi,
+    Cw83,
+    Cw83Multi,
+    Cw84,
+    Cw84Multi,
+}
+
+
+impl KnownInterface {
+
+    pub const ALL: [KnownInterface; 10] = [
+        KnownInterface::Cw1,
+        KnownInterface::Cw22,
+        KnownInterface::Cw81,
+        KnownInterface::Cw81Multi,
+        KnownInterface::Cw82,
+        KnownInterface::Cw82Multi,
+        KnownInterface::Cw83,
+        KnownInterface::Cw83Multi,
+        KnownInterface::Cw84,
+        KnownInterface::Cw84Multi,
+    ];
+
+    /// Canonical name of the interface, e.g. "crates:cw81-multi"
+    pub fn name(&self) -> &'static str {
+        match self {
+            KnownInterface::Cw1         => "crates:cw1",
+            KnownInterface::Cw22        => "crates:cw22",
+            KnownInterface::Cw81        => "crates:cw81",
+            KnownInterface::Cw81Multi   => "crates:cw81-multi",
+            KnownInterface::Cw82        => "crates:cw82",
+            KnownInterface::Cw82Multi   => "crates:cw82-multi",
+            KnownInterface::Cw83        => "crates:cw83",
+            KnownInterface::Cw83Multi   => "crates:cw83-multi",
+            KnownInterface::Cw84        => "crates:cw84",
+            KnownInterface::Cw84Multi   => "crates:cw84-multi",
+        }
+    }
+
+    /// Version of the interface that the contracts built against cw-extra should declare
+    pub fn version(&self) -> &'static str {
+        match self {
+            KnownInterface::Cw1 => CW1_VERSION,
+            _ => CW_EXTRA_VERSION,
+        }
+    }
+
+    /// Whether the interface includes the batch variants of the `multi` feature
+    pub fn is_multi(&self) -> bool {
+        self.name().ends_with(MULTI_SUFFIX)
+    }
+
+    /// Same interface without the batch variants
+    pub fn base(&self) -> KnownInterface {
+        match self {
+            KnownInterface::Cw81Multi => KnownInterface::Cw81,
+            KnownInterface::Cw82Multi => KnownInterface::Cw82,
+            KnownInterface::Cw83Multi => KnownInterface::Cw83,
+            KnownInterface::Cw84Multi => KnownInterface::Cw84,
+            other => *other,
+        }
+    }
+
+    /// Same interface with the batch variants if the standard defines them
+    pub fn multi(&self) -> Option<KnownInterface> {
+        match self.base() {
+            KnownInterface::Cw81 => Some(KnownInterface::Cw81Multi),
+            KnownInterface::Cw82 => Some(KnownInterface::Cw82Multi),
+            KnownInterface::Cw83 => Some(KnownInterface::Cw83Multi),
+            KnownInterface::Cw84 => Some(KnownInterface::Cw84Multi),
+            _ => None,
+        }
+    }
+
+    /// All the names under which the interface might have been stored, canonical one first
+    pub fn aliases(&self) -> Vec<String> {
+        let name = self.name();
+        let short = &name[CANONICAL_PREFIX.len()..];
+        std::iter::once(name.to_string())
+            .chain(ALIAS_PREFIXES.iter().map(|prefix| format!("{prefix}{short}")))
+            .collect()
+    }
+
+    /// Whether the given name refers to this interface under any of the aliases
+    pub fn matches(&self, name: &str) -> bool {
+        name.parse::<KnownInterface>().is_ok_and(|known| known == *self)
+    }
+
+    /// Entry to pass to `set_contract_supported_interface`
+    pub fn supported_interface(&self) -> ContractSupportedInterface<'static> {
+        ContractSupportedInterface {
+            supported_interface: Cow::Borrowed(self.name()),
+            version: Cow::Borrowed(self.version()),
+        }
+    }
+}
+
+
+impl fmt::Display for KnownInterface {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+
+impl FromStr for KnownInterface {
+    type Err = Cw22Error;
+
+    /// Accepts the canonical names, the aliases with other registry domains and the bare crate names
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        let short = std::iter::once(CANONICAL_PREFIX)
+            .chain(ALIAS_PREFIXES)
+            .find_map(|prefix| name.strip_prefix(prefix))
+            .unwrap_or(name);
+
+        KnownInterface::ALL
+            .into_iter()
+            .find(|known| &known.name()[CANONICAL_PREFIX.len()..] == short)
+            .ok_or_else(|| Cw22Error::UnknownInterface { interface: name.to_string() })
+    }
+}
+
+
+impl From<KnownInterface> for ContractSupportedInterface<'static> {
+    fn from(known: KnownInterface) -> Self {
+        known.supported_interface()
+    }
+}
+
+
+/// Canonical form of an interface name if the interface is known or the name itself otherwise
+pub fn normalize_interface_name(name: &str) -> Cow<'_, str> {
+    match name.parse::<KnownInterface>() {
+        Ok(known) => Cow::Borrowed(known.name()),
+        Err(_) => Cow::Borrowed(name),
+    }
+}
+
+
+/// All the names under which an interface might have been stored, canonical one first
+pub fn interface_aliases(name: &str) -> Vec<String> {
+    match name.parse::<KnownInterface>() {
+        Ok(known) => known.aliases(),
+        Err(_) => vec![name.to_string()],
+    }
+}
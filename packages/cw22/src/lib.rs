@@ -9,14 +9,35 @@
 
 //! The version string for each interface follows Semantic Versioning standard. More info is in:
 //! https://docs.rs/semver/latest/semver/
+mod error;
+mod interfaces;
+mod msg;
+mod probe;
+mod schema;
+
 use cosmwasm_schema::cw_serde;
-use types::wasm::{QuerierWrapper, QueryRequest, StdError, StdResult, Storage, WasmQuery, Map};
+use types::wasm::{from_json, generic_err, QuerierWrapper, StdResult, Storage, Map, Bound, Order};
 use semver::{Version, VersionReq};
 use std::borrow::Cow;
 
+pub use error::Cw22Error;
+pub use interfaces::*;
+pub use msg::*;
+pub use probe::*;
+pub use schema::*;
+pub use types::{supported_interface_query, supports};
+/// Cosmwasm types of the version that cw22 is built with
+pub use types::wasm;
+
+pub const INTERFACE_NAME: &str = "crates:cw22";
+pub const INTERFACE_VERSION: &str = env!("CARGO_PKG_VERSION");
+
 pub const INTERFACE_NAMESPACE: &str = "supported_interfaces";
 pub const SUPPORTED_INTERFACES: Map<&str, String> = Map::new(INTERFACE_NAMESPACE);
 
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+
 
 #[cw_serde]
 pub struct ContractSupportedInterface<'a> {
@@ -34,46 +55,129 @@ pub struct ContractSupportedInterface<'a> {
 
 /// set_contract_supported_interface should be used in instantiate to store the original version
 /// of supported interfaces. It should also be used after every migration.
+/// Nothing is written to the storage if any of the versions is invalid.
+/// Entries stored under an alias of the interface are replaced by the canonical name.
 pub fn set_contract_supported_interface(
     store: &mut dyn Storage,
     supported_interfaces: &[ContractSupportedInterface],
-) -> StdResult<()> {
+) -> Result<(), Cw22Error> {
+    validate_versions(supported_interfaces)?;
     for item in supported_interfaces {
-        let ver = Version::parse(&item.version);
-        match ver {
-            Ok(_) => {
-                SUPPORTED_INTERFACES.save(
-                    store,
-                    &item.supported_interface,
-                    &item.version.to_string(),
-                )?;
-            }
-            Err(_) => {
-                return Err(StdError::msg("Version's format is invalid"));
+        remove_supported_interface(store, &item.supported_interface);
+        SUPPORTED_INTERFACES.save(
+            store,
+            &normalize_interface_name(&item.supported_interface),
+            &item.version.to_string(),
+        )?;
+    }
+    Ok(())
+}
+
+
+/// remove_supported_interface drops an interface that the contract no longer implements
+/// under any of its aliases. Removing an interface that has never been set is a no-op.
+pub fn remove_supported_interface(
+    store: &mut dyn Storage,
+    supported_interface: &str,
+) {
+    for name in interface_aliases(supported_interface) {
+        SUPPORTED_INTERFACES.remove(store, &name);
+    }
+}
+
+
+/// replace_supported_interfaces atomically overwrites the whole set of supported interfaces.
+/// Nothing is written to the storage if any of the new versions is invalid.
+pub fn replace_supported_interfaces(
+    store: &mut dyn Storage,
+    supported_interfaces: &[ContractSupportedInterface],
+) -> Result<(), Cw22Error> {
+    validate_versions(supported_interfaces)?;
+    SUPPORTED_INTERFACES.clear(store);
+    for item in supported_interfaces {
+        SUPPORTED_INTERFACES.save(
+            store,
+            &normalize_interface_name(&item.supported_interface),
+            &item.version.to_string(),
+        )?;
+    }
+    Ok(())
+}
+
+
+/// migrate_supported_interfaces should be used in `migrate` to replace the set of supported interfaces.
+/// Every interface from the new set that is already stored must have a version matching
+/// `from_version_req` (e.g. ">=2.0.0, <2.2.0"), otherwise the migration is aborted.
+/// Interfaces missing from the new set are dropped.
+pub fn migrate_supported_interfaces(
+    store: &mut dyn Storage,
+    from_version_req: &str,
+    supported_interfaces: &[ContractSupportedInterface],
+) -> Result<(), Cw22Error> {
+    for item in supported_interfaces {
+        if let Some(version) = load_interface_version(store, &item.supported_interface)? {
+            if !try_require_version(&item.supported_interface, &version, from_version_req)? {
+                return Err(Cw22Error::VersionMismatch {
+                    interface: item.supported_interface.to_string(),
+                    version,
+                    requirement: from_version_req.to_string(),
+                });
             }
         }
     }
+    replace_supported_interfaces(store, supported_interfaces)
+}
+
+
+fn load_interface_version(store: &dyn Storage, name: &str) -> StdResult<Option<String>> {
+    for alias in interface_aliases(name) {
+        if let Some(version) = SUPPORTED_INTERFACES.may_load(store, &alias)? {
+            return Ok(Some(version));
+        }
+    }
+    Ok(None)
+}
+
+
+fn validate_versions(supported_interfaces: &[ContractSupportedInterface]) -> Result<(), Cw22Error> {
+    for item in supported_interfaces {
+        parse_version(&item.supported_interface, &item.version)?;
+    }
     Ok(())
 }
 
 
+fn parse_version(interface: &str, version: &str) -> Result<Version, Cw22Error> {
+    Version::parse(version).map_err(|_| Cw22Error::InvalidVersion {
+        interface: interface.to_string(),
+        version: version.to_string(),
+    })
+}
+
+
+fn parse_requirement(requirement: &str) -> Result<VersionReq, Cw22Error> {
+    VersionReq::parse(requirement).map_err(|_| Cw22Error::InvalidRequirement {
+        requirement: requirement.to_string(),
+    })
+}
+
+
 pub fn query_supported_interface_version(
     querier: &QuerierWrapper,
     contract_addr: &str, 
     interface_name: &str,
 ) -> StdResult<Option<String>> {
 
-    let key = types::wasm::storage_keys::namespace_with_key(
-        &[INTERFACE_NAMESPACE.as_bytes()], 
-        interface_name.as_bytes()
-    );
-
-    let raw_query = WasmQuery::Raw { 
-        contract_addr: contract_addr.into(),
-        key: key.into()
-    };
-
-    querier.query(&QueryRequest::Wasm(raw_query))
+    for alias in interface_aliases(interface_name) {
+        let key = types::wasm::storage_keys::namespace_with_key(
+            &[INTERFACE_NAMESPACE.as_bytes()], 
+            alias.as_bytes()
+        );
+        if let Some(raw) = querier.query_wasm_raw(contract_addr, key)? {
+            return from_json(raw).map(Some);
+        }
+    }
+    Ok(None)
 }
 
 
@@ -98,6 +202,101 @@ pub fn require_version(version: &str, request: &str) -> bool {
     false
 }
 
+
+/// Same as `minimum_version` but tells a malformed version or requirement apart from a version
+/// that is simply lower than required
+pub fn try_minimum_version(interface: &str, version: &str, required: &str) -> Result<bool, Cw22Error> {
+    let ver = parse_version(interface, version)?;
+    let req = parse_requirement(format!(">={}", required).as_str())?;
+    Ok(req.matches(&ver))
+}
+
+/// Same as `require_version` but tells a malformed version or requirement apart from a version
+/// that doesn't match the requirement
+pub fn try_require_version(interface: &str, version: &str, request: &str) -> Result<bool, Cw22Error> {
+    let ver = parse_version(interface, version)?;
+    let req = parse_requirement(request)?;
+    Ok(req.matches(&ver))
+}
+
+
+/// Queries the version of an interface declared by another contract and checks it against
+/// a SemVer requirement. Returns the declared version or an error describing why the contract
+/// doesn't fit: the interface isn't declared, its version is malformed or doesn't match.
+pub fn ensure_supported_interface(
+    querier: &QuerierWrapper,
+    contract_addr: &str,
+    interface_name: &str,
+    request: &str,
+) -> Result<String, Cw22Error> {
+    let version = lookup_interface_version(querier, contract_addr, interface_name)?
+        .ok_or_else(|| Cw22Error::NotSupported {
+            interface: interface_name.to_string(),
+        })?;
+
+    if !try_require_version(interface_name, &version, request)? {
+        return Err(Cw22Error::VersionMismatch {
+            interface: interface_name.to_string(),
+            version,
+            requirement: request.to_string(),
+        });
+    }
+    Ok(version)
+}
+
+
+/// Handler for `SupportedInterface` query returning the stored version of an interface
+pub fn query_supported_interface(
+    store: &dyn Storage,
+    name: &str,
+) -> StdResult<SupportedInterfaceResponse> {
+    Ok(SupportedInterfaceResponse {
+        version: load_interface_version(store, name)?,
+    })
+}
+
+
+/// Handler for `SupportedInterfaces` query returning a page of the stored interfaces
+pub fn query_supported_interfaces(
+    store: &dyn Storage,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<SupportedInterfacesResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.as_deref().map(Bound::exclusive);
+
+    let interfaces = SUPPORTED_INTERFACES
+        .range(store, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(name, version)| ContractSupportedInterface {
+            supported_interface: name.into(),
+            version: version.into(),
+        }))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(SupportedInterfacesResponse { interfaces })
+}
+
+
+/// Handler for `Supports` query checking the stored version of an interface against a SemVer requirement.
+/// A malformed requirement is reported as an error rather than as an unsupported interface
+pub fn query_supports(
+    store: &dyn Storage,
+    name: &str,
+    version_req: &str,
+) -> StdResult<SupportsInterfaceResponse> {
+    let requirement = parse_requirement(version_req)
+        .map_err(|e| generic_err(e.to_string()))?;
+
+    let supported = load_interface_version(store, name)?
+        .and_then(|version| Version::parse(&version).ok())
+        .map(|version| requirement.matches(&version))
+        .unwrap_or(false);
+
+    Ok(SupportsInterfaceResponse { supported })
+}
+
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -128,9 +327,20 @@ mod tests {
 
         let rs_error =
             set_contract_supported_interface(&mut store, supported_interface).unwrap_err();
-        let expected = StdError::msg("Version's format is invalid");
+        let expected = Cw22Error::InvalidVersion {
+            interface: interface22.to_string(),
+            version: "v0.1.0".to_string(),
+        };
         assert_eq!(expected.to_string(), rs_error.to_string());
 
+        // nothing is stored when any of the versions is invalid
+        let rs_error = set_contract_supported_interface(&mut store, &[
+            ContractSupportedInterface { supported_interface: "crates:cw81".into(), version: "1.0.0".into() },
+            ContractSupportedInterface { supported_interface: "crates:cw82".into(), version: "1.0".into() },
+        ]).unwrap_err();
+        assert!(matches!(rs_error, Cw22Error::InvalidVersion { .. }));
+        assert_eq!(SUPPORTED_INTERFACES.may_load(&store, "crates:cw81").unwrap(), None);
+
         // set supported_interface
         let supported_interface = &[contract_interface2, contract_interface22];
 
@@ -146,6 +356,104 @@ mod tests {
         */
     }
 
+    #[test]
+    fn query_handlers_work() {
+        let mut store = MockStorage::new();
+
+        set_contract_supported_interface(&mut store, &[
+            ContractSupportedInterface {
+                supported_interface: "crates:cw81".into(),
+                version: "2.2.0".into(),
+            },
+            ContractSupportedInterface {
+                supported_interface: "crates:cw82".into(),
+                version: "2.1.0".into(),
+            },
+            ContractSupportedInterface {
+                supported_interface: "crates:cw84".into(),
+                version: "2.0.0".into(),
+            },
+        ]).unwrap();
+
+        let res = query_supported_interface(&store, "crates:cw81").unwrap();
+        assert_eq!(res.version, Some("2.2.0".to_string()));
+
+        let res = query_supported_interface(&store, "crates:cw83").unwrap();
+        assert_eq!(res.version, None);
+
+        let res = query_supported_interfaces(&store, None, None).unwrap();
+        assert_eq!(res.interfaces.len(), 3);
+
+        let res = query_supported_interfaces(&store, Some("crates:cw81".into()), Some(1)).unwrap();
+        assert_eq!(res.interfaces.len(), 1);
+        assert_eq!(res.interfaces[0].supported_interface, "crates:cw82");
+        assert_eq!(res.interfaces[0].version, "2.1.0");
+
+        assert!(query_supports(&store, "crates:cw82", ">=2.0.0").unwrap().supported);
+        assert!(!query_supports(&store, "crates:cw82", ">=2.2.0").unwrap().supported);
+        assert!(!query_supports(&store, "crates:cw83", "*").unwrap().supported);
+
+        // a malformed requirement is an error, not an unsupported interface
+        let err = query_supports(&store, "crates:cw82", ">=two").unwrap_err();
+        assert!(err.to_string().contains("Version requirement `>=two` is invalid"));
+        assert!(query_supports(&store, "crates:cw83", "not a req").is_err());
+    }
+
+    #[test]
+    fn remove_replace_and_migrate_work() {
+        let mut store = MockStorage::new();
+
+        set_contract_supported_interface(&mut store, &[
+            ContractSupportedInterface {
+                supported_interface: "crates:cw81".into(),
+                version: "2.1.0".into(),
+            },
+            ContractSupportedInterface {
+                supported_interface: "crates:cw1".into(),
+                version: "1.1.1".into(),
+            },
+        ]).unwrap();
+
+        remove_supported_interface(&mut store, "crates:cw1");
+        remove_supported_interface(&mut store, "crates:cw83");
+        assert_eq!(query_supported_interface(&store, "crates:cw1").unwrap().version, None);
+
+        // invalid version leaves the storage untouched
+        let err = replace_supported_interfaces(&mut store, &[
+            ContractSupportedInterface {
+                supported_interface: "crates:cw82".into(),
+                version: "2.2.0".into(),
+            },
+            ContractSupportedInterface {
+                supported_interface: "crates:cw84".into(),
+                version: "v2.2.0".into(),
+            },
+        ]).unwrap_err();
+        assert!(matches!(err, Cw22Error::InvalidVersion { .. }));
+        assert_eq!(query_supported_interfaces(&store, None, None).unwrap().interfaces.len(), 1);
+
+        let new_set = [
+            ContractSupportedInterface {
+                supported_interface: "crates:cw81".into(),
+                version: "2.2.0".into(),
+            },
+            ContractSupportedInterface {
+                supported_interface: "crates:cw82".into(),
+                version: "2.2.0".into(),
+            },
+        ];
+
+        // stored cw81 doesn't match the requirement
+        let err = migrate_supported_interfaces(&mut store, ">=2.2.0", &new_set).unwrap_err();
+        assert!(matches!(err, Cw22Error::VersionMismatch { .. }));
+        assert_eq!(query_supported_interface(&store, "crates:cw81").unwrap().version, Some("2.1.0".into()));
+
+        migrate_supported_interfaces(&mut store, ">=2.0.0, <2.2.0", &new_set).unwrap();
+        let res = query_supported_interfaces(&store, None, None).unwrap();
+        assert_eq!(res.interfaces.len(), 2);
+        assert!(res.interfaces.iter().all(|i| i.version == "2.2.0"));
+    }
+
     #[test]
     fn test_require_version() {
         let version_req = ">=0.1.0";
@@ -169,6 +477,209 @@ mod tests {
         assert!(!result);
     }
 
+    #[test]
+    fn probe_interfaces_work() {
+        use types::wasm::{
+            from_json, to_json_binary, testing::MockQuerier, Binary, ContractResult,
+            QuerierWrapper, SystemError, SystemResult, WasmQuery,
+        };
+
+        let mut store = MockStorage::new();
+        set_contract_supported_interface(&mut store, &[
+            ContractSupportedInterface {
+                supported_interface: "crates:cw81".into(),
+                version: "2.2.0".into(),
+            },
+            ContractSupportedInterface {
+                supported_interface: "crates:cw82".into(),
+                version: "1.0.0".into(),
+            },
+        ]).unwrap();
+
+        let mut querier: MockQuerier = MockQuerier::new(&[]);
+        querier.update_wasm(move |query| match query {
+            // contract with the default storage layout
+            WasmQuery::Raw { contract_addr, key } if contract_addr == "raw" => {
+                let value = types::wasm::Storage::get(&store, key.as_slice()).unwrap_or_default();
+                SystemResult::Ok(ContractResult::Ok(Binary::from(value)))
+            },
+            // address without a contract
+            WasmQuery::Raw { contract_addr, .. } if contract_addr == "missing" => {
+                SystemResult::Err(SystemError::NoSuchContract { addr: contract_addr.clone() })
+            },
+            WasmQuery::Raw { .. } => SystemResult::Ok(ContractResult::Ok(Binary::default())),
+            // contract with a custom storage layout that answers the smart query
+            WasmQuery::Smart { contract_addr, msg } if contract_addr == "smart" => {
+                let version = match from_json(msg).unwrap() {
+                    Cw22QueryMsg::SupportedInterface { name } if name == "crates:cw81" => Some("2.1.0".to_string()),
+                    _ => None,
+                };
+                SystemResult::Ok(ContractResult::Ok(
+                    to_json_binary(&SupportedInterfaceResponse { version }).unwrap()
+                ))
+            },
+            _ => SystemResult::Err(SystemError::Unknown {}),
+        });
+        let querier = QuerierWrapper::<types::wasm::Empty>::new(&querier);
+
+        let requirements = [
+            InterfaceRequirement { name: "crates:cw81".into(), version_req: ">=2.0.0".into() },
+            InterfaceRequirement { name: "crates:cw82".into(), version_req: "^1".into() },
+        ];
+
+        let matrix = probe_interfaces(&querier, &["raw", "smart", "none", "missing"], &requirements).unwrap();
+        assert_eq!(matrix.len(), 4);
+
+        assert!(matrix[0].compatible());
+        assert_eq!(matrix[0].interfaces[1].version, Some("1.0.0".to_string()));
+
+        assert!(!matrix[1].compatible());
+        assert!(matrix[1].interfaces[0].compatible);
+        assert_eq!(matrix[1].interfaces[0].version, Some("2.1.0".to_string()));
+        assert_eq!(matrix[1].interfaces[1].version, None);
+
+        assert!(matrix[2].interfaces.iter().all(|i| i.version.is_none() && !i.compatible));
+        assert_eq!(matrix[3].contract, "missing");
+        assert!(matrix[3].interfaces.iter().all(|i| i.version.is_none() && !i.compatible));
+
+        let invalid = [InterfaceRequirement { name: "crates:cw81".into(), version_req: "=>2".into() }];
+        let err = probe_interfaces(&querier, &["raw"], &invalid).unwrap_err();
+        assert!(matches!(err, Cw22Error::InvalidRequirement { .. }));
+
+        let err = ensure_supported_interface(&querier, "smart", "crates:cw82", "*").unwrap_err();
+        assert!(matches!(err, Cw22Error::NotSupported { .. }));
+        let err = ensure_supported_interface(&querier, "raw", "crates:cw82", ">=2.0.0").unwrap_err();
+        assert!(matches!(err, Cw22Error::VersionMismatch { .. }));
+        assert_eq!(ensure_supported_interface(&querier, "raw", "crates:cw81", "^2").unwrap(), "2.2.0");
+    }
+
+    #[test]
+    fn known_interfaces_work() {
+        assert_eq!("crates:cw82".parse::<KnownInterface>().unwrap(), KnownInterface::Cw82);
+        assert_eq!("crates.io:cw82".parse::<KnownInterface>().unwrap(), KnownInterface::Cw82);
+        assert_eq!("cw81-multi".parse::<KnownInterface>().unwrap(), KnownInterface::Cw81Multi);
+        assert!(matches!(
+            "crates:cw721".parse::<KnownInterface>().unwrap_err(),
+            Cw22Error::UnknownInterface { .. }
+        ));
+
+        assert_eq!(KnownInterface::Cw84Multi.to_string(), "crates:cw84-multi");
+        assert!(KnownInterface::Cw84Multi.is_multi());
+        assert_eq!(KnownInterface::Cw84Multi.base(), KnownInterface::Cw84);
+        assert_eq!(KnownInterface::Cw83.multi(), Some(KnownInterface::Cw83Multi));
+        assert_eq!(KnownInterface::Cw22.multi(), None);
+        assert!(KnownInterface::Cw82.matches("crates.io:cw82"));
+        assert!(!KnownInterface::Cw82.matches("crates:cw82-multi"));
+
+        assert_eq!(normalize_interface_name("crates.io:cw82"), "crates:cw82");
+        assert_eq!(normalize_interface_name("crates.io:cw2"), "crates.io:cw2");
+
+        let mut store = MockStorage::new();
+        // stored by an older version under an alias
+        SUPPORTED_INTERFACES.save(&mut store, "crates.io:cw82", &"2.0.0".to_string()).unwrap();
+        set_contract_supported_interface(&mut store, &[
+            ContractSupportedInterface {
+                supported_interface: "crates.io:cw81".into(),
+                version: "2.2.0".into(),
+            },
+        ]).unwrap();
+
+        assert_eq!(SUPPORTED_INTERFACES.may_load(&store, "crates:cw81").unwrap(), Some("2.2.0".into()));
+        assert_eq!(query_supported_interface(&store, "crates:cw82").unwrap().version, Some("2.0.0".into()));
+        assert!(query_supports(&store, KnownInterface::Cw82.name(), "^2").unwrap().supported);
+
+        // setting the canonical name replaces the entry stored under the alias
+        set_contract_supported_interface(&mut store, &[KnownInterface::Cw82.supported_interface()]).unwrap();
+        assert_eq!(SUPPORTED_INTERFACES.may_load(&store, "crates.io:cw82").unwrap(), None);
+        let listed = query_supported_interfaces(&store, None, None).unwrap().interfaces;
+        assert_eq!(
+            listed.iter().map(|i| i.supported_interface.as_ref()).collect::<Vec<_>>(),
+            vec!["crates:cw81", "crates:cw82"]
+        );
+        assert_eq!(query_supported_interface(&store, "crates.io:cw82").unwrap().version, Some(KnownInterface::Cw82.version().into()));
+
+        remove_supported_interface(&mut store, "crates:cw82");
+        assert_eq!(query_supported_interface(&store, "crates.io:cw82").unwrap().version, None);
+    }
+
+    #[test]
+    fn check_interface_variants_work() {
+        use cosmwasm_schema::{schema_for, QueryResponses};
+        use types::wasm::{Binary, CosmosMsg};
+
+        #[cw_serde]
+        #[derive(QueryResponses)]
+        enum QueryMsg {
+            #[returns(bool)]
+            CanExecute { sender: String, msg: CosmosMsg },
+            #[returns(bool)]
+            ValidSignature { data: Binary, signature: Binary, payload: Option<Binary> },
+        }
+
+        #[cw_serde]
+        enum ExecuteMsg {
+            Execute { msgs: Vec<CosmosMsg> },
+            Freeze,
+        }
+
+        let query_schema = schema_for!(QueryMsg);
+        let execute_schema = schema_for!(ExecuteMsg);
+
+        assert_eq!(schema_variants(&query_schema), vec!["can_execute", "valid_signature"]);
+        assert_eq!(schema_variants(&execute_schema), vec!["execute", "freeze"]);
+
+        let declared = [
+            KnownInterface::Cw1.supported_interface(),
+            KnownInterface::Cw81.supported_interface(),
+            KnownInterface::Cw82Multi.supported_interface(),
+            KnownInterface::Cw84.supported_interface(),
+            ContractSupportedInterface {
+                supported_interface: "crates.io:cw2".into(),
+                version: "1.0.0".into(),
+            },
+        ];
+
+        let missing = check_interface_variants(&declared, &query_schema, Some(&execute_schema));
+        assert_eq!(missing, vec![
+            MissingVariants {
+                interface: "crates:cw82-multi".into(),
+                query: vec!["valid_signatures".into()],
+                execute: vec![],
+            },
+            MissingVariants {
+                interface: "crates:cw84".into(),
+                query: vec!["can_execute_signed".into()],
+                execute: vec!["execute_signed".into()],
+            },
+        ]);
+
+        let missing = check_interface_variants(&declared[..2], &query_schema, None);
+        assert_eq!(missing, vec![
+            MissingVariants {
+                interface: "crates:cw1".into(),
+                query: vec![],
+                execute: vec!["execute".into()],
+            },
+        ]);
+    }
+
+    #[test]
+    fn test_try_versions() {
+        assert!(try_require_version("crates:cw81", "2.2.0", ">=2.0.0").unwrap());
+        assert!(!try_require_version("crates:cw81", "1.0.0", ">=2.0.0").unwrap());
+        assert!(try_minimum_version("crates:cw81", "2.2.0", "2.1.0").unwrap());
+        assert!(!try_minimum_version("crates:cw81", "2.0.0", "2.1.0").unwrap());
+
+        let err = try_require_version("crates:cw81", "v2.2.0", ">=2.0.0").unwrap_err();
+        assert!(matches!(err, Cw22Error::InvalidVersion { .. }));
+
+        let err = try_require_version("crates:cw81", "2.2.0", "=>2.0.0").unwrap_err();
+        assert!(matches!(err, Cw22Error::InvalidRequirement { .. }));
+
+        let err = try_minimum_version("crates:cw81", "2.2.0", "v2.0.0").unwrap_err();
+        assert!(matches!(err, Cw22Error::InvalidRequirement { .. }));
+    }
+
     #[test]
     fn test_minimum_version() {
         let result = minimum_version("0.16.0", "0.2.3");
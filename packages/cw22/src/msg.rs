@@ -0,0 +1,55 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use crate::ContractSupportedInterface;
+
+
+/// Query messages that any contract declaring its interfaces through cw22 can answer
+/// without relying on raw storage queries
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum Cw22QueryMsg {
+
+    /// Version of a single interface if the contract supports it
+    #[returns(SupportedInterfaceResponse)]
+    SupportedInterface {
+        name        :   String,
+    },
+
+    /// List of all interfaces declared by the contract
+    #[returns(SupportedInterfacesResponse)]
+    SupportedInterfaces {
+        /// Name of an interface to start after
+        start_after :   Option<String>,
+        /// Number of interfaces to return
+        limit       :   Option<u32>,
+    },
+
+    /// Whether the contract supports an interface with a version matching
+    /// a SemVer requirement, e.g. ">=1.2.3, <1.8.0"
+    #[returns(SupportsInterfaceResponse)]
+    Supports {
+        name        :   String,
+        version_req :   String,
+    },
+}
+
+
+/// Response type for `SupportedInterface` query
+#[cw_serde]
+pub struct SupportedInterfaceResponse {
+    /// Version of the interface or `None` if the contract doesn't declare it
+    pub version: Option<String>,
+}
+
+
+/// Response type for `SupportedInterfaces` query
+#[cw_serde]
+pub struct SupportedInterfacesResponse {
+    pub interfaces: Vec<ContractSupportedInterface<'static>>,
+}
+
+
+/// Response type for `Supports` query
+#[cw_serde]
+pub struct SupportsInterfaceResponse {
+    pub supported: bool,
+}
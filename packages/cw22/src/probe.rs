@@ -0,0 +1,111 @@
+use cosmwasm_schema::cw_serde;
+use semver::{Version, VersionReq};
+use types::wasm::{QuerierWrapper, StdResult};
+
+use crate::{interface_aliases, parse_requirement, query_supported_interface_version, Cw22Error, Cw22QueryMsg, SupportedInterfaceResponse};
+
+
+/// Interface that a contract is expected to support with a version matching `version_req`
+#[cw_serde]
+pub struct InterfaceRequirement {
+    /// Name of the interface, e.g. "crates:cw81"
+    pub name: String,
+    /// SemVer requirement for the version of the interface, e.g. ">=2.0.0, <3.0.0"
+    pub version_req: String,
+}
+
+
+/// Result of probing a single interface of a contract
+#[cw_serde]
+pub struct InterfaceProbe {
+    /// Name of the probed interface
+    pub name: String,
+    /// Version declared by the contract or `None` if the interface isn't declared
+    pub version: Option<String>,
+    /// Whether the declared version matches the requirement
+    pub compatible: bool,
+}
+
+
+/// Row of the compatibility matrix returned by `probe_interfaces`
+#[cw_serde]
+pub struct ContractProbe {
+    /// Address of the probed contract
+    pub contract: String,
+    /// Results for every requirement in the same order they were passed
+    pub interfaces: Vec<InterfaceProbe>,
+}
+
+impl ContractProbe {
+    /// Whether the contract matches every requirement
+    pub fn compatible(&self) -> bool {
+        self.interfaces.iter().all(|i| i.compatible)
+    }
+}
+
+
+/// Looks up the version of an interface declared by a contract under any of its aliases. Reads the raw keys of
+/// `SUPPORTED_INTERFACES` first and falls back to the `SupportedInterface` smart query when the keys are missing,
+/// e.g. for contracts with a custom storage layout. Contracts that answer neither are treated as not declaring the interface.
+pub fn lookup_interface_version(
+    querier: &QuerierWrapper,
+    contract_addr: &str,
+    interface_name: &str,
+) -> StdResult<Option<String>> {
+    if let Some(version) = query_supported_interface_version(querier, contract_addr, interface_name)? {
+        return Ok(Some(version));
+    }
+    for alias in interface_aliases(interface_name) {
+        let res: StdResult<SupportedInterfaceResponse> = querier.query_wasm_smart(
+            contract_addr,
+            &Cw22QueryMsg::SupportedInterface { name: alias },
+        );
+        if let Some(version) = res.ok().and_then(|r| r.version) {
+            return Ok(Some(version));
+        }
+    }
+    Ok(None)
+}
+
+
+/// Checks many contracts against many interface requirements at once and returns
+/// a compatibility matrix with a row for each contract in the same order they were passed.
+/// Contracts that can't be queried, e.g. addresses without a contract, get a row without
+/// any declared interface instead of failing the whole probe
+pub fn probe_interfaces(
+    querier: &QuerierWrapper,
+    contracts: &[&str],
+    requirements: &[InterfaceRequirement],
+) -> Result<Vec<ContractProbe>, Cw22Error> {
+    let parsed = requirements
+        .iter()
+        .map(|r| parse_requirement(&r.version_req))
+        .collect::<Result<Vec<VersionReq>, Cw22Error>>()?;
+
+    let matrix = contracts
+        .iter()
+        .map(|contract| ContractProbe {
+            contract: contract.to_string(),
+            interfaces: requirements
+                .iter()
+                .zip(parsed.iter())
+                .map(|(requirement, req)| {
+                    let version = lookup_interface_version(querier, contract, &requirement.name)
+                        .unwrap_or_default();
+                    let compatible = version
+                        .as_deref()
+                        .and_then(|v| Version::parse(v).ok())
+                        .map(|v| req.matches(&v))
+                        .unwrap_or(false);
+                    InterfaceProbe {
+                        name: requirement.name.clone(),
+                        version,
+                        compatible,
+                    }
+                })
+                .collect(),
+        })
+        .collect();
+
+    Ok(matrix)
+}
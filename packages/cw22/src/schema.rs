@@ -0,0 +1,122 @@
+use cosmwasm_schema::{cw_serde, schemars::schema::{RootSchema, Schema, SchemaObject}};
+
+use crate::{ContractSupportedInterface, KnownInterface};
+
+
+/// Variants required by a declared interface that are missing from the contract's messages
+#[cw_serde]
+pub struct MissingVariants {
+    /// Name of the interface as it has been declared
+    pub interface: String,
+    /// Missing variants of the query message
+    pub query: Vec<String>,
+    /// Missing variants of the execute message
+    pub execute: Vec<String>,
+}
+
+
+impl KnownInterface {
+
+    /// Variants of the query message required by the interface in snake case
+    pub fn query_variants(&self) -> &'static [&'static str] {
+        match self {
+            KnownInterface::Cw1         => &["can_execute"],
+            KnownInterface::Cw22        => &[],
+            KnownInterface::Cw81        => &["valid_signature"],
+            KnownInterface::Cw81Multi   => &["valid_signature", "valid_signatures"],
+            KnownInterface::Cw82        => &["can_execute", "valid_signature"],
+            KnownInterface::Cw82Multi   => &["can_execute", "valid_signature", "valid_signatures"],
+            KnownInterface::Cw83        => &["account_info"],
+            KnownInterface::Cw83Multi   => &["account_info", "accounts"],
+            KnownInterface::Cw84        => &["can_execute", "can_execute_signed", "valid_signature"],
+            KnownInterface::Cw84Multi   => &["can_execute", "can_execute_signed", "valid_signature", "valid_signatures"],
+        }
+    }
+
+    /// Variants of the execute message required by the interface in snake case
+    pub fn execute_variants(&self) -> &'static [&'static str] {
+        match self.base() {
+            KnownInterface::Cw1 | KnownInterface::Cw82  => &["execute"],
+            KnownInterface::Cw83                        => &["create_account"],
+            KnownInterface::Cw84                        => &["execute", "execute_signed"],
+            _ => &[],
+        }
+    }
+}
+
+
+/// Sorted names of the variants of a message enum from its JSON schema, e.g. generated with `schema_for!(QueryMsg)`
+pub fn schema_variants(schema: &RootSchema) -> Vec<String> {
+    let mut variants = Vec::new();
+    collect_variants(&schema.schema, &mut variants);
+    variants.sort();
+    variants.dedup();
+    variants
+}
+
+
+/// Cross-checks the interfaces declared by a contract against the JSON schemas of its messages and reports
+/// the variants missing for every interface. Interfaces unknown to cw22 are skipped. Meant to be used in tests:
+///
+/// ```rust,ignore
+/// let declared = cw22::query_supported_interfaces(deps.as_ref().storage, None, None)?.interfaces;
+/// let missing = cw22::check_interface_variants(
+///     &declared,
+///     &schema_for!(QueryMsg),
+///     Some(&schema_for!(ExecuteMsg)),
+/// );
+/// assert!(missing.is_empty(), "{missing:?}");
+/// ```
+pub fn check_interface_variants(
+    declared: &[ContractSupportedInterface],
+    query_schema: &RootSchema,
+    execute_schema: Option<&RootSchema>,
+) -> Vec<MissingVariants> {
+    let query_variants = schema_variants(query_schema);
+    let execute_variants = execute_schema.map(schema_variants).unwrap_or_default();
+
+    let missing = |required: &[&str], present: &[String]| -> Vec<String> {
+        required
+            .iter()
+            .filter(|variant| !present.iter().any(|p| p == *variant))
+            .map(|variant| variant.to_string())
+            .collect()
+    };
+
+    declared
+        .iter()
+        .filter_map(|item| {
+            let known = item.supported_interface.parse::<KnownInterface>().ok()?;
+            let query = missing(known.query_variants(), &query_variants);
+            let execute = missing(known.execute_variants(), &execute_variants);
+            if query.is_empty() && execute.is_empty() {
+                return None;
+            }
+            Some(MissingVariants {
+                interface: item.supported_interface.to_string(),
+                query,
+                execute,
+            })
+        })
+        .collect()
+}
+
+
+fn collect_variants(schema: &SchemaObject, variants: &mut Vec<String>) {
+    // unit variants are serialized as plain strings
+    if let Some(values) = &schema.enum_values {
+        variants.extend(values.iter().filter_map(|v| v.as_str().map(String::from)));
+    }
+    // struct and tuple variants are objects with a single required property
+    if let Some(object) = &schema.object {
+        variants.extend(object.required.iter().cloned());
+    }
+    if let Some(subschemas) = &schema.subschemas {
+        let nested = subschemas.one_of.iter().chain(subschemas.any_of.iter()).flatten();
+        for sub in nested {
+            if let Schema::Object(sub) = sub {
+                collect_variants(sub, variants);
+            }
+        }
+    }
+}
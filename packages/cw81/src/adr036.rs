@@ -0,0 +1,127 @@
+use bech32::{Bech32, Hrp};
+use cosmwasm_schema::{cw_serde, serde::Serialize};
+use ripemd::Ripemd160;
+use sha2::{Digest, Sha256};
+use types::{AuthPayload, wasm::{generic_err, to_json_vec, Api, Binary, StdResult}};
+
+use crate::SignatureVerifier;
+
+
+/// Verifies signatures of arbitrary data produced according to
+/// [ADR-036](https://github.com/cosmos/cosmos-sdk/blob/main/docs/architecture/adr-036-arbitrary-signature.md),
+/// e.g. by `signArbitrary` of Keplr or Leap. The amino sign doc is rebuilt from the data and the address
+/// derived from the secp256k1 public key with the given human readable prefix
+#[cw_serde]
+pub struct Adr036Verifier {
+    pub hrp: String,
+}
+
+
+impl Adr036Verifier {
+
+    pub fn new(hrp: impl Into<String>) -> Self {
+        Self { hrp: hrp.into() }
+    }
+
+    /// Takes the prefix from the `hrp` of the payload
+    pub fn from_payload(payload: &AuthPayload) -> StdResult<Self> {
+        payload.hrp
+            .as_ref()
+            .map(Self::new)
+            .ok_or_else(|| generic_err("ADR-036 verification requires `hrp` in the payload"))
+    }
+
+    /// Serialized sign doc that a wallet signs when asked to sign the data
+    pub fn sign_doc(&self, data: &[u8], pubkey: &[u8]) -> StdResult<Vec<u8>> {
+        let signer = pubkey_to_address(pubkey, &self.hrp)?;
+        adr036_sign_doc(data, &signer)
+    }
+}
+
+
+impl SignatureVerifier for Adr036Verifier {
+    fn verify(&self, api: &dyn Api, data: &[u8], signature: &[u8], pubkey: &[u8]) -> StdResult<bool> {
+        let doc = self.sign_doc(data, pubkey)?;
+        let hash : [u8; 32] = Sha256::digest(doc).into();
+        Ok(api.secp256k1_verify(&hash, signature, pubkey)?)
+    }
+}
+
+
+/// Amino JSON of an ADR-036 `StdSignDoc` with a single `sign/MsgSignData` message.
+/// Fields are declared in alphabetical order to match the canonical serialization
+pub fn adr036_sign_doc(data: &[u8], signer: &str) -> StdResult<Vec<u8>> {
+    to_json_vec(&StdSignDoc {
+        account_number: "0".into(),
+        chain_id: "".into(),
+        fee: StdFee { amount: vec![], gas: "0".into() },
+        memo: "".into(),
+        msgs: vec![AminoMsg {
+            msg_type: "sign/MsgSignData".into(),
+            value: MsgSignData {
+                data: Binary::from(data),
+                signer: signer.to_string(),
+            },
+        }],
+        sequence: "0".into(),
+    })
+}
+
+
+/// Bech32 address of a secp256k1 public key in the format used by the Cosmos SDK chains.
+/// Accepts both compressed and uncompressed keys
+pub fn pubkey_to_address(pubkey: &[u8], hrp: &str) -> StdResult<String> {
+    let compressed = compress_secp256k1_pubkey(pubkey)?;
+    let hash = Ripemd160::digest(Sha256::digest(compressed));
+    let hrp = Hrp::parse(hrp).map_err(|e| generic_err(format!("Invalid hrp: {e}")))?;
+    bech32::encode::<Bech32>(hrp, &hash).map_err(|e| generic_err(format!("Invalid address: {e}")))
+}
+
+
+fn compress_secp256k1_pubkey(pubkey: &[u8]) -> StdResult<Vec<u8>> {
+    match pubkey.len() {
+        33 => Ok(pubkey.to_vec()),
+        65 if pubkey[0] == 0x04 => {
+            let prefix = 0x02 | (pubkey[64] & 1);
+            let mut compressed = Vec::with_capacity(33);
+            compressed.push(prefix);
+            compressed.extend_from_slice(&pubkey[1..33]);
+            Ok(compressed)
+        }
+        len => Err(generic_err(format!("Invalid secp256k1 public key length: {len}"))),
+    }
+}
+
+
+#[derive(Serialize)]
+#[serde(crate = "cosmwasm_schema::serde")]
+struct StdSignDoc {
+    account_number: String,
+    chain_id: String,
+    fee: StdFee,
+    memo: String,
+    msgs: Vec<AminoMsg>,
+    sequence: String,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "cosmwasm_schema::serde")]
+struct StdFee {
+    amount: Vec<String>,
+    gas: String,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "cosmwasm_schema::serde")]
+struct AminoMsg {
+    #[serde(rename = "type")]
+    msg_type: String,
+    value: MsgSignData,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "cosmwasm_schema::serde")]
+struct MsgSignData {
+    data: Binary,
+    signer: String,
+}
@@ -0,0 +1,97 @@
+use cosmwasm_schema::serde::Serialize;
+#[cfg(feature = "cosmwasm")]
+use cosmwasm_schema::cw_serde;
+use cw22::{lookup_interface_version, Cw22Error, KnownInterface};
+use types::wasm::{generic_err, Addr, Binary, QuerierWrapper};
+
+use crate::{query_valid_signature, query_valid_signatures};
+
+
+/// Wrapper around the address of a contract implementing cw81 for calling it from other contracts.
+/// The support of the interface is checked through cw22 before every verification
+#[cfg_attr(feature = "cosmwasm", cw_serde)]
+#[cfg_attr(not(feature = "cosmwasm"), derive(
+    cosmwasm_schema::serde::Serialize,
+    cosmwasm_schema::serde::Deserialize,
+    Clone, Debug, PartialEq,
+    cosmwasm_schema::schemars::JsonSchema,
+))]
+#[cfg_attr(not(feature = "cosmwasm"), serde(crate = "cosmwasm_schema::serde"))]
+#[cfg_attr(not(feature = "cosmwasm"), schemars(crate = "cosmwasm_schema::schemars"))]
+pub struct Cw81Contract(pub Addr);
+
+
+impl Cw81Contract {
+
+    pub fn new(addr: Addr) -> Self {
+        Self(addr)
+    }
+
+    pub fn addr(&self) -> Addr {
+        self.0.clone()
+    }
+
+    /// Whether the contract declares cw81 with or without the batch variants
+    pub fn is_supported(&self, querier: &QuerierWrapper) -> Result<bool, Cw22Error> {
+        Ok(self.declares(querier, KnownInterface::Cw81)? || self.is_multi(querier)?)
+    }
+
+    /// Whether the contract declares the `multi` variant of cw81 with `ValidSignatures`
+    pub fn is_multi(&self, querier: &QuerierWrapper) -> Result<bool, Cw22Error> {
+        self.declares(querier, KnownInterface::Cw81Multi)
+    }
+
+    /// Queries `ValidSignature` of the contract. The payload must be of the type the contract expects
+    pub fn valid_signature<P: Serialize>(
+        &self,
+        querier: &QuerierWrapper,
+        data: &[u8],
+        signature: &[u8],
+        payload: Option<&P>,
+    ) -> Result<bool, Cw22Error> {
+        self.ensure_supported(querier)?;
+        Ok(query_valid_signature(querier, self.0.as_str(), data, signature, payload)?)
+    }
+
+    /// Queries `ValidSignatures` of contracts with the `multi` variant and
+    /// falls back to querying `ValidSignature` for every pair otherwise
+    pub fn valid_signatures<P: Serialize>(
+        &self,
+        querier: &QuerierWrapper,
+        data: &[Binary],
+        signatures: &[Binary],
+        payload: Option<&P>,
+    ) -> Result<Vec<bool>, Cw22Error> {
+        if data.len() != signatures.len() {
+            return Err(generic_err(format!("{} signatures for {} data entries", signatures.len(), data.len())).into());
+        }
+        // declaring the `multi` variant implies the support of cw81
+        if self.is_multi(querier)? {
+            return Ok(query_valid_signatures(querier, self.0.as_str(), data, signatures, payload)?);
+        }
+        if !self.declares(querier, KnownInterface::Cw81)? {
+            return Err(not_supported());
+        }
+        data.iter()
+            .zip(signatures.iter())
+            .map(|(data, signature)| Ok(query_valid_signature(querier, self.0.as_str(), data, signature, payload)?))
+            .collect()
+    }
+
+    /// Looks the interface up under its canonical name and the legacy aliases
+    fn declares(&self, querier: &QuerierWrapper, interface: KnownInterface) -> Result<bool, Cw22Error> {
+        Ok(lookup_interface_version(querier, self.0.as_str(), interface.name())?.is_some())
+    }
+
+    fn ensure_supported(&self, querier: &QuerierWrapper) -> Result<(), Cw22Error> {
+        if !self.is_supported(querier)? {
+            return Err(not_supported());
+        }
+        Ok(())
+    }
+}
+
+
+fn not_supported() -> Cw22Error {
+    Cw22Error::NotSupported { interface: KnownInterface::Cw81.name().to_string() }
+}
@@ -0,0 +1,125 @@
+use cosmwasm_schema::serde::Serialize;
+use types::{AuthPayload, ValidSignaturesResponse, wasm::{
+    generic_err, Api, Binary, ContractInfoResponse, QuerierWrapper, StdResult, WasmQuery
+}};
+
+use crate::{SignatureVerifier, ValidSignatureResponse};
+
+
+/// Default number of nested contracts a signature can be forwarded through
+pub const DEFAULT_MAX_DEPTH: u32 = 3;
+
+
+/// `Cw81QueryMsg` with a payload of any type
+#[derive(Serialize)]
+#[serde(crate = "cosmwasm_schema::serde", rename_all = "snake_case")]
+enum ValidSignatureQuery<'a, P> {
+    ValidSignature {
+        data: Binary,
+        signature: Binary,
+        payload: Option<&'a P>,
+    },
+    ValidSignatures {
+        data: &'a [Binary],
+        signatures: &'a [Binary],
+        payload: Option<&'a P>,
+    },
+}
+
+
+/// Asks another contract whether the signature of the data is valid using its cw81 `ValidSignature` query.
+/// The payload is passed as is so it must be of the type the contract expects, e.g. `AuthPayload` or `Binary`
+pub fn query_valid_signature<P: Serialize>(
+    querier: &QuerierWrapper,
+    contract_addr: &str,
+    data: &[u8],
+    signature: &[u8],
+    payload: Option<&P>,
+) -> StdResult<bool> {
+    let res : ValidSignatureResponse = querier.query_wasm_smart(contract_addr, &ValidSignatureQuery::ValidSignature {
+        data: data.into(),
+        signature: signature.into(),
+        payload,
+    })?;
+    Ok(res.is_valid)
+}
+
+
+/// Same as `query_valid_signature` but uses the `ValidSignatures` query of contracts with the `multi` feature
+pub fn query_valid_signatures<P: Serialize>(
+    querier: &QuerierWrapper,
+    contract_addr: &str,
+    data: &[Binary],
+    signatures: &[Binary],
+    payload: Option<&P>,
+) -> StdResult<Vec<bool>> {
+    let res : ValidSignaturesResponse = querier.query_wasm_smart(contract_addr, &ValidSignatureQuery::ValidSignatures {
+        data,
+        signatures,
+        payload,
+    })?;
+    Ok(res.are_valid)
+}
+
+
+/// Verifies signatures of credentials that are contract addresses by forwarding them to the `ValidSignature`
+/// query of the contract, similarly to ERC-1271 signers that are contracts themselves. Other credentials are
+/// checked by the inner verifier. The forwarded payload keeps the fields of the original one, extension included,
+/// except the `credential_id` identifying the contract among the credentials of the caller, and counts the contracts
+/// in `delegation_depth`. Signatures passing through more contracts than `max_depth` are rejected to prevent loops
+pub struct DelegatingVerifier<'a, V: SignatureVerifier> {
+    pub querier: QuerierWrapper<'a>,
+    pub inner: V,
+    pub depth: u32,
+    pub max_depth: u32,
+    /// Payload of the caller forwarded to the contracts
+    pub payload: Option<AuthPayload>,
+}
+
+
+impl<'a, V: SignatureVerifier> DelegatingVerifier<'a, V> {
+
+    pub fn new(querier: QuerierWrapper<'a>, inner: V, max_depth: u32) -> Self {
+        Self { querier, inner, depth: 0, max_depth, payload: None }
+    }
+
+    /// Continues from the `delegation_depth` of the payload when the contract is itself being delegated to
+    pub fn from_payload(querier: QuerierWrapper<'a>, inner: V, max_depth: u32, payload: Option<&AuthPayload>) -> Self {
+        let depth = payload
+            .and_then(|p| p.delegation_depth)
+            .unwrap_or_default();
+        Self { querier, inner, depth, max_depth, payload: payload.cloned() }
+    }
+
+    /// Address of the credential if it's one. Whether it belongs to a contract is checked before forwarding
+    fn address(&self, api: &dyn Api, pubkey: &[u8]) -> Option<String> {
+        let addr = std::str::from_utf8(pubkey).ok()?;
+        api.addr_validate(addr).ok().map(|addr| addr.to_string())
+    }
+}
+
+
+impl<V: SignatureVerifier> SignatureVerifier for DelegatingVerifier<'_, V> {
+    /// `pubkey` is either the address of a contract encoded as UTF-8 or a credential of the inner verifier
+    fn verify(&self, api: &dyn Api, data: &[u8], signature: &[u8], pubkey: &[u8]) -> StdResult<bool> {
+        let contract = match self.address(api, pubkey) {
+            Some(contract) => contract,
+            None => return self.inner.verify(api, data, signature, pubkey),
+        };
+        // accounts of users have no code to ask
+        self.querier
+            .query::<ContractInfoResponse>(&WasmQuery::ContractInfo { contract_addr: contract.clone() }.into())
+            .map_err(|_| generic_err(format!("Credential `{contract}` is not a contract")))?;
+        if self.depth >= self.max_depth {
+            return Err(generic_err(format!("Signature can't be forwarded through more than {} contracts", self.max_depth)));
+        }
+        let original = self.payload.clone().unwrap_or(AuthPayload { 
+            credential_id: None, 
+            hrp: None, 
+            extension: None, 
+            delegation_depth: None 
+        });
+        let payload = AuthPayload { credential_id: None, delegation_depth: Some(self.depth + 1), ..original };
+        query_valid_signature(&self.querier, &contract, data, signature, Some(&payload))
+    }
+}
@@ -0,0 +1,75 @@
+use cosmwasm_schema::cw_serde;
+use thiserror::Error;
+use types::wasm::StdError;
+
+
+#[derive(Error, Debug)]
+pub enum VerificationError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Malformed signature: {reason}")]
+    MalformedSignature {
+        reason: String,
+    },
+
+    #[error("Public key of {length} bytes is invalid, expected {expected}")]
+    InvalidKeyLength {
+        length: usize,
+        expected: String,
+    },
+
+    #[error("Signature doesn't match the data")]
+    HashMismatch,
+
+    #[error("Signature has expired")]
+    Expired,
+
+    #[error("Credential `{credential_id}` is unknown")]
+    UnknownCredential {
+        credential_id: String,
+    },
+
+    #[error("Credential `{credential_id}` has been revoked")]
+    Revoked {
+        credential_id: String,
+    },
+
+    #[error("Signatures have a weight of {weight} while {threshold} is required")]
+    ThresholdNotMet {
+        weight: u64,
+        threshold: u64,
+    },
+}
+
+
+/// Machine readable reason of a rejected signature
+#[cw_serde]
+#[derive(Copy, Eq)]
+pub enum VerificationFailure {
+    MalformedSignature,
+    InvalidKeyLength,
+    HashMismatch,
+    Expired,
+    UnknownCredential,
+    Revoked,
+    ThresholdNotMet,
+    /// Any other error, e.g. a malformed payload
+    Other,
+}
+
+
+impl VerificationError {
+    pub fn failure(&self) -> VerificationFailure {
+        match self {
+            VerificationError::Std(_) => VerificationFailure::Other,
+            VerificationError::MalformedSignature { .. } => VerificationFailure::MalformedSignature,
+            VerificationError::InvalidKeyLength { .. } => VerificationFailure::InvalidKeyLength,
+            VerificationError::HashMismatch => VerificationFailure::HashMismatch,
+            VerificationError::Expired => VerificationFailure::Expired,
+            VerificationError::UnknownCredential { .. } => VerificationFailure::UnknownCredential,
+            VerificationError::Revoked { .. } => VerificationFailure::Revoked,
+            VerificationError::ThresholdNotMet { .. } => VerificationFailure::ThresholdNotMet,
+        }
+    }
+}
@@ -0,0 +1,195 @@
+#[cfg(feature = "cosmwasm")]
+use cosmwasm_schema::cw_serde;
+use sha3::{Digest, Keccak256};
+use types::wasm::{generic_err, Api, Binary, StdResult};
+
+use crate::SignatureVerifier;
+
+
+const EIP191_PREFIX: &str = "\x19Ethereum Signed Message:\n";
+
+
+/// Verifies `personal_sign` signatures of Ethereum wallets as described in
+/// [EIP-191](https://eips.ethereum.org/EIPS/eip-191). The signer is recovered from
+/// the signature and compared against the expected 20-byte address
+pub struct Eip191Verifier;
+
+impl SignatureVerifier for Eip191Verifier {
+    /// `pubkey` is either the 20-byte address or the uncompressed public key of the signer
+    fn verify(&self, api: &dyn Api, data: &[u8], signature: &[u8], pubkey: &[u8]) -> StdResult<bool> {
+        verify_eth_signature(api, &eip191_hash(data), signature, pubkey)
+    }
+}
+
+
+/// Domain of the typed data described in [EIP-712](https://eips.ethereum.org/EIPS/eip-712).
+/// Only the fields that are set are included in the domain separator
+// `Binary` of older versions and of Secret Network doesn't implement `Schemaifier` required by `cw_serde`
+#[cfg_attr(feature = "cosmwasm", cw_serde)]
+#[cfg_attr(not(feature = "cosmwasm"), derive(
+    cosmwasm_schema::serde::Serialize,
+    cosmwasm_schema::serde::Deserialize,
+    Clone, Debug, PartialEq,
+    cosmwasm_schema::schemars::JsonSchema,
+))]
+#[cfg_attr(not(feature = "cosmwasm"), serde(crate = "cosmwasm_schema::serde"))]
+#[cfg_attr(not(feature = "cosmwasm"), schemars(crate = "cosmwasm_schema::schemars"))]
+#[derive(Default)]
+pub struct Eip712Domain {
+    pub name: Option<String>,
+    pub version: Option<String>,
+    pub chain_id: Option<u64>,
+    /// Hex encoded address with or without `0x` prefix
+    pub verifying_contract: Option<String>,
+    pub salt: Option<Binary>,
+}
+
+
+impl Eip712Domain {
+
+    /// `hashStruct` of the `EIP712Domain`
+    pub fn separator(&self) -> StdResult<[u8; 32]> {
+        let mut types = Vec::with_capacity(5);
+        let mut fields = Vec::with_capacity(5);
+
+        if let Some(name) = &self.name {
+            types.push("string name");
+            fields.push(keccak256(name.as_bytes()));
+        }
+        if let Some(version) = &self.version {
+            types.push("string version");
+            fields.push(keccak256(version.as_bytes()));
+        }
+        if let Some(chain_id) = self.chain_id {
+            types.push("uint256 chainId");
+            let mut word = [0u8; 32];
+            word[24..].copy_from_slice(&chain_id.to_be_bytes());
+            fields.push(word);
+        }
+        if let Some(contract) = &self.verifying_contract {
+            types.push("address verifyingContract");
+            let mut word = [0u8; 32];
+            word[12..].copy_from_slice(&parse_eth_address(contract)?);
+            fields.push(word);
+        }
+        if let Some(salt) = &self.salt {
+            types.push("bytes32 salt");
+            fields.push(salt.as_slice().try_into().map_err(|_| generic_err("EIP-712 salt must be 32 bytes"))?);
+        }
+
+        Ok(hash_struct(&format!("EIP712Domain({})", types.join(",")), &fields))
+    }
+}
+
+
+/// Verifies `eth_signTypedData_v4` signatures as described in [EIP-712](https://eips.ethereum.org/EIPS/eip-712).
+/// The signed data is expected to be the 32-byte `hashStruct` of the message which can be computed with `hash_struct`
+#[cfg_attr(feature = "cosmwasm", cw_serde)]
+#[cfg_attr(not(feature = "cosmwasm"), derive(
+    cosmwasm_schema::serde::Serialize,
+    cosmwasm_schema::serde::Deserialize,
+    Clone, Debug, PartialEq,
+    cosmwasm_schema::schemars::JsonSchema,
+))]
+#[cfg_attr(not(feature = "cosmwasm"), serde(crate = "cosmwasm_schema::serde"))]
+#[cfg_attr(not(feature = "cosmwasm"), schemars(crate = "cosmwasm_schema::schemars"))]
+pub struct Eip712Verifier {
+    pub domain: Eip712Domain,
+}
+
+
+impl Eip712Verifier {
+    pub fn new(domain: Eip712Domain) -> Self {
+        Self { domain }
+    }
+}
+
+
+impl SignatureVerifier for Eip712Verifier {
+    /// `pubkey` is either the 20-byte address or the uncompressed public key of the signer
+    fn verify(&self, api: &dyn Api, data: &[u8], signature: &[u8], pubkey: &[u8]) -> StdResult<bool> {
+        let struct_hash : [u8; 32] = data
+            .try_into()
+            .map_err(|_| generic_err("EIP-712 data must be the 32-byte hash of the message"))?;
+        let hash = eip712_hash(&self.domain.separator()?, &struct_hash);
+        verify_eth_signature(api, &hash, signature, pubkey)
+    }
+}
+
+
+pub fn keccak256(data: &[u8]) -> [u8; 32] {
+    Keccak256::digest(data).into()
+}
+
+
+/// Hash of the data prefixed according to EIP-191 that is signed by `personal_sign`
+pub fn eip191_hash(data: &[u8]) -> [u8; 32] {
+    let mut hasher = Keccak256::new();
+    hasher.update(EIP191_PREFIX.as_bytes());
+    hasher.update(data.len().to_string().as_bytes());
+    hasher.update(data);
+    hasher.finalize().into()
+}
+
+
+/// Final EIP-712 digest of a message given the domain separator and the `hashStruct` of the message
+pub fn eip712_hash(domain_separator: &[u8; 32], struct_hash: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Keccak256::new();
+    hasher.update([0x19, 0x01]);
+    hasher.update(domain_separator);
+    hasher.update(struct_hash);
+    hasher.finalize().into()
+}
+
+
+/// EIP-712 `hashStruct` of a message given its encoded type, e.g. "Mail(address from,address to,string contents)",
+/// and the values already encoded into 32-byte words in the order of the type
+pub fn hash_struct(encoded_type: &str, fields: &[[u8; 32]]) -> [u8; 32] {
+    let mut hasher = Keccak256::new();
+    hasher.update(keccak256(encoded_type.as_bytes()));
+    for field in fields {
+        hasher.update(field);
+    }
+    hasher.finalize().into()
+}
+
+
+/// 20-byte Ethereum address of an uncompressed secp256k1 public key
+pub fn eth_address(pubkey: &[u8]) -> StdResult<[u8; 20]> {
+    if pubkey.len() != 65 || pubkey[0] != 0x04 {
+        return Err(generic_err("Ethereum address can only be derived from an uncompressed public key"));
+    }
+    let hash = keccak256(&pubkey[1..]);
+    let mut address = [0u8; 20];
+    address.copy_from_slice(&hash[12..]);
+    Ok(address)
+}
+
+
+/// Parses a hex encoded Ethereum address with or without `0x` prefix
+pub fn parse_eth_address(address: &str) -> StdResult<[u8; 20]> {
+    let stripped = address.strip_prefix("0x").unwrap_or(address);
+    let mut bytes = [0u8; 20];
+    hex::decode_to_slice(stripped, &mut bytes)
+        .map_err(|_| generic_err(format!("Invalid Ethereum address: {address}")))?;
+    Ok(bytes)
+}
+
+
+/// Recovers the signer of a 65-byte `r || s || v` signature and compares it with the expected signer
+pub(crate) fn verify_eth_signature(api: &dyn Api, hash: &[u8], signature: &[u8], expected: &[u8]) -> StdResult<bool> {
+    if signature.len() != 65 {
+        return Err(generic_err(format!("Invalid Ethereum signature length: {}", signature.len())));
+    }
+    let expected = match expected.len() {
+        20 => expected.try_into().unwrap(),
+        _ => eth_address(expected)?,
+    };
+    let recovery_param = match signature[64] {
+        0 | 27 => 0,
+        1 | 28 => 1,
+        v => return Err(generic_err(format!("Invalid recovery id: {v}"))),
+    };
+    let recovered = api.secp256k1_recover_pubkey(hash, &signature[..64], recovery_param)?;
+    Ok(eth_address(&recovered)? == expected)
+}
@@ -1,18 +1,41 @@
-use cosmwasm_schema::{cw_serde, QueryResponses};
+mod error;
+mod verify;
+mod adr036;
+mod eth;
+// querying contracts on Secret Network requires their code hashes
+#[cfg(any(feature = "cosmwasm", feature = "cosmwasm_v1"))]
+mod delegate;
+#[cfg(feature = "client")]
+mod client;
+#[cfg(feature = "cosmwasm")]
+mod webauthn;
+
+use cosmwasm_schema::cw_serde;
+#[cfg(feature = "cosmwasm")]
+use cosmwasm_schema::QueryResponses;
 use types::wasm::Binary;
 
 
 pub const INTERFACE_NAME: &str = "crates:cw81";
+pub const INTERFACE_VERSION: &str = env!("CARGO_PKG_VERSION");
 #[cfg(feature = "multi")]
 pub const INTERFACE_NAME_MULTI: &str = "crates:cw81-multi";
 
 
 /// Absolute minimum of a query that follows cw82 standard
-#[cw_serde]
-#[derive(QueryResponses)]
+// `Binary` of older versions and of Secret Network doesn't implement `Schemaifier` required by `cw_serde`
+#[cfg_attr(feature = "cosmwasm", cw_serde, derive(QueryResponses))]
+#[cfg_attr(not(feature = "cosmwasm"), derive(
+    cosmwasm_schema::serde::Serialize,
+    cosmwasm_schema::serde::Deserialize,
+    Clone, Debug, PartialEq,
+    cosmwasm_schema::schemars::JsonSchema,
+))]
+#[cfg_attr(not(feature = "cosmwasm"), serde(crate = "cosmwasm_schema::serde", rename_all = "snake_case"))]
+#[cfg_attr(not(feature = "cosmwasm"), schemars(crate = "cosmwasm_schema::schemars"))]
 pub enum Cw81QueryMsg {
 
-    #[returns(ValidSignatureResponse)]
+    #[cfg_attr(feature = "cosmwasm", returns(ValidSignatureResponse))]
     ValidSignature {
         data        :   Binary,
         signature   :   Binary,
@@ -20,7 +43,7 @@ pub enum Cw81QueryMsg {
     },
 
     #[cfg(feature = "multi")]
-    #[returns(ValidSignaturesResponse)]
+    #[cfg_attr(feature = "cosmwasm", returns(ValidSignaturesResponse))]
     ValidSignatures {
         data        :   Vec<Binary>,
         signatures  :   Vec<Binary>,
@@ -29,8 +52,51 @@ pub enum Cw81QueryMsg {
 
 }
 
+/// Response of the optional `ValidSignatureDetailed` query
+#[cw_serde]
+pub struct ValidSignatureDetailedResponse {
+    pub is_valid: bool,
+    /// Why the signature was rejected
+    pub failure: Option<VerificationFailure>,
+    /// Human readable description of the failure
+    pub reason: Option<String>,
+    /// Credential that the signature was verified with if the contract has many
+    pub credential_id: Option<String>,
+}
+
+
+impl ValidSignatureDetailedResponse {
+    /// Builds the response from the result of a verification and the matched credential
+    pub fn from_result(result: Result<Option<String>, VerificationError>) -> Self {
+        match result {
+            Ok(credential_id) => Self {
+                is_valid: true,
+                failure: None,
+                reason: None,
+                credential_id,
+            },
+            Err(err) => Self {
+                is_valid: false,
+                failure: Some(err.failure()),
+                reason: Some(err.to_string()),
+                credential_id: None,
+            },
+        }
+    }
+}
+
 // No execute message is defined for cw81
 
-pub use types::{valid_signature_query, ValidSignatureResponse};
+pub use error::*;
+pub use verify::*;
+pub use adr036::*;
+pub use eth::*;
+#[cfg(any(feature = "cosmwasm", feature = "cosmwasm_v1"))]
+pub use delegate::*;
+#[cfg(feature = "client")]
+pub use client::*;
+#[cfg(feature = "cosmwasm")]
+pub use webauthn::*;
+pub use types::{valid_signature_query, valid_signature_detailed_query, AuthPayload, ValidSignatureResponse};
 #[cfg(feature = "multi")]
 pub use types::ValidSignaturesResponse;
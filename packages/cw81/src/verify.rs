@@ -0,0 +1,351 @@
+use cosmwasm_schema::cw_serde;
+use sha2::{Digest, Sha256, Sha512};
+use types::{AuthPayload, wasm::{from_json, generic_err, Api, Binary, StdResult}};
+
+use crate::{eth::verify_eth_signature, keccak256, Eip191Verifier, VerificationError};
+
+
+/// Type of a stored public key or address used to pick a signature verifier
+#[cw_serde]
+#[derive(Copy, Eq, Default)]
+pub enum KeyType {
+    #[default]
+    Secp256k1,
+    /// Used by passkeys and other WebAuthn authenticators
+    #[cfg(feature = "cosmwasm")]
+    Secp256r1,
+    Ed25519,
+    /// 20-byte address of an Ethereum account signing with `personal_sign`
+    Ethereum,
+}
+
+
+/// Common interface for checking signatures of arbitrary data against a public key.
+/// ECDSA verifiers hash the data with sha256 before the verification while ed25519 signs the raw data
+pub trait SignatureVerifier {
+
+    /// Whether the signature of the data belongs to the public key. Errors if the inputs are malformed
+    fn verify(
+        &self,
+        api: &dyn Api,
+        data: &[u8],
+        signature: &[u8],
+        pubkey: &[u8],
+    ) -> StdResult<bool>;
+
+    /// Checks a batch of data and signatures against the same public key and returns
+    /// the result for each pair. Errors if the lengths of the lists don't match
+    fn verify_batch(
+        &self,
+        api: &dyn Api,
+        data: &[Binary],
+        signatures: &[Binary],
+        pubkey: &[u8],
+    ) -> StdResult<Vec<bool>> {
+        ensure_same_length(data, signatures)?;
+        Ok(data
+            .iter()
+            .zip(signatures.iter())
+            .map(|(data, signature)| self.is_valid(api, data, signature, pubkey))
+            .collect())
+    }
+
+    /// Same as `verify` but explains why the signature was rejected
+    fn check(
+        &self,
+        api: &dyn Api,
+        data: &[u8],
+        signature: &[u8],
+        pubkey: &[u8],
+    ) -> Result<(), VerificationError> {
+        match self.verify(api, data, signature, pubkey)? {
+            true => Ok(()),
+            false => Err(VerificationError::HashMismatch),
+        }
+    }
+
+    /// Same as `verify` but treats malformed inputs as invalid signatures
+    fn is_valid(
+        &self,
+        api: &dyn Api,
+        data: &[u8],
+        signature: &[u8],
+        pubkey: &[u8],
+    ) -> bool {
+        self.verify(api, data, signature, pubkey).unwrap_or(false)
+    }
+}
+
+
+pub struct Secp256k1Verifier;
+
+impl SignatureVerifier for Secp256k1Verifier {
+    fn verify(&self, api: &dyn Api, data: &[u8], signature: &[u8], pubkey: &[u8]) -> StdResult<bool> {
+        Ok(api.secp256k1_verify(&sha256(data), signature, pubkey)?)
+    }
+}
+
+
+#[cfg(feature = "cosmwasm")]
+pub struct Secp256r1Verifier;
+
+#[cfg(feature = "cosmwasm")]
+impl SignatureVerifier for Secp256r1Verifier {
+    fn verify(&self, api: &dyn Api, data: &[u8], signature: &[u8], pubkey: &[u8]) -> StdResult<bool> {
+        Ok(api.secp256r1_verify(&sha256(data), signature, pubkey)?)
+    }
+}
+
+
+/// Verifies signatures of the raw data. Use `HashedVerifier` when the data is hashed before signing
+pub struct Ed25519Verifier;
+
+impl SignatureVerifier for Ed25519Verifier {
+    fn verify(&self, api: &dyn Api, data: &[u8], signature: &[u8], pubkey: &[u8]) -> StdResult<bool> {
+        Ok(api.ed25519_verify(data, signature, pubkey)?)
+    }
+
+    /// Uses `ed25519_batch_verify` and only falls back to checking the signatures
+    /// one by one when some of them are invalid
+    fn verify_batch(
+        &self,
+        api: &dyn Api,
+        data: &[Binary],
+        signatures: &[Binary],
+        pubkey: &[u8],
+    ) -> StdResult<Vec<bool>> {
+        ensure_same_length(data, signatures)?;
+        let messages : Vec<&[u8]> = data.iter().map(|d| d.as_slice()).collect();
+        Ok(ed25519_verify_batch(api, &messages, signatures, pubkey))
+    }
+}
+
+
+impl KeyType {
+    /// Verifier for the keys of this type
+    pub fn verifier(&self) -> &'static dyn SignatureVerifier {
+        match self {
+            KeyType::Secp256k1 => &Secp256k1Verifier,
+            #[cfg(feature = "cosmwasm")]
+            KeyType::Secp256r1 => &Secp256r1Verifier,
+            KeyType::Ed25519 => &Ed25519Verifier,
+            KeyType::Ethereum => &Eip191Verifier,
+        }
+    }
+
+    /// Verifier for the keys of this type that hashes the data with the given mode
+    pub fn with_hash_mode(&self, hash_mode: HashMode) -> HashedVerifier {
+        HashedVerifier { key_type: *self, hash_mode }
+    }
+
+    /// Checks that the signature and the public key have the lengths expected for the keys of this type
+    pub fn check_lengths(&self, signature: &[u8], pubkey: &[u8]) -> Result<(), VerificationError> {
+        let (signature_len, pubkey_lens, expected) : (usize, &[usize], &str) = match self {
+            KeyType::Secp256k1 => (64, &[33, 65], "33 or 65"),
+            #[cfg(feature = "cosmwasm")]
+            KeyType::Secp256r1 => (64, &[33, 65], "33 or 65"),
+            KeyType::Ed25519 => (64, &[32], "32"),
+            KeyType::Ethereum => (65, &[20, 65], "20 or 65"),
+        };
+        if signature.len() != signature_len {
+            return Err(VerificationError::MalformedSignature {
+                reason: format!("expected {signature_len} bytes, got {}", signature.len()),
+            });
+        }
+        if !pubkey_lens.contains(&pubkey.len()) {
+            return Err(VerificationError::InvalidKeyLength {
+                length: pubkey.len(),
+                expected: expected.to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Checks the signature of an already computed digest
+    pub fn verify_digest(&self, api: &dyn Api, digest: &[u8], signature: &[u8], pubkey: &[u8]) -> StdResult<bool> {
+        match self {
+            KeyType::Secp256k1 => Ok(api.secp256k1_verify(digest, signature, pubkey)?),
+            #[cfg(feature = "cosmwasm")]
+            KeyType::Secp256r1 => Ok(api.secp256r1_verify(digest, signature, pubkey)?),
+            KeyType::Ed25519 => Ok(api.ed25519_verify(digest, signature, pubkey)?),
+            KeyType::Ethereum => verify_eth_signature(api, digest, signature, pubkey),
+        }
+    }
+}
+
+
+impl SignatureVerifier for KeyType {
+    fn verify(&self, api: &dyn Api, data: &[u8], signature: &[u8], pubkey: &[u8]) -> StdResult<bool> {
+        self.verifier().verify(api, data, signature, pubkey)
+    }
+
+    fn check(&self, api: &dyn Api, data: &[u8], signature: &[u8], pubkey: &[u8]) -> Result<(), VerificationError> {
+        self.check_lengths(signature, pubkey)?;
+        self.verifier().check(api, data, signature, pubkey)
+    }
+
+    fn verify_batch(
+        &self,
+        api: &dyn Api,
+        data: &[Binary],
+        signatures: &[Binary],
+        pubkey: &[u8],
+    ) -> StdResult<Vec<bool>> {
+        self.verifier().verify_batch(api, data, signatures, pubkey)
+    }
+}
+
+
+/// How the data is turned into the digest that gets signed
+#[cw_serde]
+#[derive(Copy, Eq, Default)]
+pub enum HashMode {
+    /// The data is already a digest and is verified as is
+    Prehashed,
+    #[default]
+    Sha256,
+    Keccak256,
+    /// Produces 64-byte digests that can only be used with ed25519 keys
+    Sha512,
+    /// Double sha256 of the data prefixed with "Bitcoin Signed Message:\n" and its length as done by
+    /// `signmessage` of Bitcoin wallets. Recovery headers of 65-byte signatures of secp256k1 keys are skipped
+    /// while the ones of Ethereum keys keep the recovery id at the end needed to recover the address
+    BitcoinMessage,
+}
+
+
+/// Extension of `AuthPayload` selecting the hash mode for a verification
+#[cw_serde]
+pub struct HashExtension {
+    pub hash_mode: HashMode,
+}
+
+
+impl HashMode {
+
+    /// Digest of the data according to the mode
+    pub fn hash(&self, data: &[u8]) -> Vec<u8> {
+        match self {
+            HashMode::Prehashed => data.to_vec(),
+            HashMode::Sha256 => sha256(data).to_vec(),
+            HashMode::Keccak256 => keccak256(data).to_vec(),
+            HashMode::Sha512 => Sha512::digest(data).to_vec(),
+            HashMode::BitcoinMessage => {
+                let mut message = Vec::with_capacity(BITCOIN_PREFIX.len() + 9 + data.len());
+                message.extend_from_slice(BITCOIN_PREFIX);
+                message.extend_from_slice(&compact_size(data.len()));
+                message.extend_from_slice(data);
+                sha256(&sha256(&message)).to_vec()
+            },
+        }
+    }
+
+    /// Mode requested in the `extension` of the payload if it is a `HashExtension`
+    pub fn from_payload(payload: Option<&AuthPayload>) -> Option<HashMode> {
+        payload
+            .and_then(|p| p.extension.as_ref())
+            .and_then(|ext| from_json::<HashExtension>(ext).ok())
+            .map(|ext| ext.hash_mode)
+    }
+
+    fn signature<'a>(&self, key_type: KeyType, signature: &'a [u8]) -> &'a [u8] {
+        match (self, key_type) {
+            (HashMode::BitcoinMessage, KeyType::Secp256k1) if signature.len() == 65 => &signature[1..],
+            _ => signature,
+        }
+    }
+}
+
+
+/// Verifier of the keys of a given type that hashes the data according to the hash mode
+#[cw_serde]
+#[derive(Copy, Eq)]
+pub struct HashedVerifier {
+    pub key_type: KeyType,
+    pub hash_mode: HashMode,
+}
+
+
+impl SignatureVerifier for HashedVerifier {
+    fn verify(&self, api: &dyn Api, data: &[u8], signature: &[u8], pubkey: &[u8]) -> StdResult<bool> {
+        let digest = self.hash_mode.hash(data);
+        self.key_type.verify_digest(api, &digest, self.hash_mode.signature(self.key_type, signature), pubkey)
+    }
+
+    fn check(&self, api: &dyn Api, data: &[u8], signature: &[u8], pubkey: &[u8]) -> Result<(), VerificationError> {
+        self.key_type.check_lengths(self.hash_mode.signature(self.key_type, signature), pubkey)?;
+        match self.verify(api, data, signature, pubkey)? {
+            true => Ok(()),
+            false => Err(VerificationError::HashMismatch),
+        }
+    }
+
+    fn verify_batch(
+        &self,
+        api: &dyn Api,
+        data: &[Binary],
+        signatures: &[Binary],
+        pubkey: &[u8],
+    ) -> StdResult<Vec<bool>> {
+        ensure_same_length(data, signatures)?;
+        if self.key_type != KeyType::Ed25519 {
+            return Ok(data
+                .iter()
+                .zip(signatures.iter())
+                .map(|(data, signature)| self.is_valid(api, data, signature, pubkey))
+                .collect());
+        }
+        let digests : Vec<Vec<u8>> = data.iter().map(|d| self.hash_mode.hash(d)).collect();
+        let messages : Vec<&[u8]> = digests.iter().map(|d| d.as_slice()).collect();
+        Ok(ed25519_verify_batch(api, &messages, signatures, pubkey))
+    }
+}
+
+
+const BITCOIN_PREFIX: &[u8] = b"\x18Bitcoin Signed Message:\n";
+
+
+/// Variable length integer used by Bitcoin for the length of the message
+fn compact_size(len: usize) -> Vec<u8> {
+    match len {
+        0..=0xfc => vec![len as u8],
+        0xfd..=0xffff => [&[0xfd], &(len as u16).to_le_bytes()[..]].concat(),
+        0x10000..=0xffff_ffff => [&[0xfe], &(len as u32).to_le_bytes()[..]].concat(),
+        _ => [&[0xff], &(len as u64).to_le_bytes()[..]].concat(),
+    }
+}
+
+
+/// Uses `ed25519_batch_verify` and only falls back to checking the signatures
+/// one by one when some of them are invalid
+fn ed25519_verify_batch(api: &dyn Api, messages: &[&[u8]], signatures: &[Binary], pubkey: &[u8]) -> Vec<bool> {
+    let sigs : Vec<&[u8]> = signatures.iter().map(|s| s.as_slice()).collect();
+    let keys : Vec<&[u8]> = vec![pubkey; messages.len()];
+
+    if api.ed25519_batch_verify(messages, &sigs, &keys).unwrap_or(false) {
+        return vec![true; messages.len()];
+    }
+
+    messages
+        .iter()
+        .zip(sigs.iter())
+        .map(|(msg, sig)| api.ed25519_verify(msg, sig, pubkey).unwrap_or(false))
+        .collect()
+}
+
+
+fn sha256(data: &[u8]) -> [u8; 32] {
+    Sha256::digest(data).into()
+}
+
+
+fn ensure_same_length(data: &[Binary], signatures: &[Binary]) -> StdResult<()> {
+    if data.len() != signatures.len() {
+        return Err(generic_err(format!(
+            "Number of signatures ({}) doesn't match the number of data entries ({})",
+            signatures.len(),
+            data.len()
+        )));
+    }
+    Ok(())
+}
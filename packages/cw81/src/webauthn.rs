@@ -0,0 +1,173 @@
+use cosmwasm_schema::cw_serde;
+use sha2::{Digest, Sha256};
+use types::{AuthPayload, wasm::{from_json, Api, Binary, StdError, StdResult}};
+
+use crate::SignatureVerifier;
+
+
+/// Flag of the authenticator data telling that the user was present
+const FLAG_USER_PRESENT: u8 = 0x01;
+/// Flag of the authenticator data telling that the user was verified, e.g. with biometrics or a PIN
+const FLAG_USER_VERIFIED: u8 = 0x04;
+/// rpIdHash (32) + flags (1) + signCount (4)
+const MIN_AUTH_DATA_LEN: usize = 37;
+
+
+/// Relying party settings that the assertions must match
+#[cw_serde]
+pub struct WebAuthnConfig {
+    /// Expected `origin` of the client data, e.g. "https://app.example.com"
+    pub origin: String,
+    /// Expected relying party id whose hash is the beginning of the authenticator data, e.g. "example.com"
+    pub rp_id: String,
+    /// Whether the user verification flag must be set in addition to the user presence one
+    pub user_verification: bool,
+}
+
+
+/// Extension of `AuthPayload` carrying the parts of a WebAuthn assertion other than the signature
+#[cw_serde]
+pub struct PasskeyExtension {
+    /// `authenticatorData` of the assertion response
+    pub authenticator_data: Binary,
+    /// `clientDataJSON` of the assertion response
+    pub client_data_json: Binary,
+}
+
+
+/// Subset of the `clientDataJSON` fields used for the verification
+#[cw_serde]
+pub struct ClientData {
+    #[serde(rename = "type")]
+    pub ty: String,
+    pub challenge: String,
+    pub origin: String,
+    #[serde(rename = "crossOrigin", default, skip_serializing_if = "Option::is_none")]
+    pub cross_origin: Option<bool>,
+}
+
+
+/// Verifies WebAuthn assertions made by passkeys with secp256r1 keys. The challenge of the assertion must be
+/// the base64url encoding of the data. Signatures can be either DER encoded as returned by the authenticators or
+/// in the raw `r || s` format
+#[cw_serde]
+pub struct WebAuthnVerifier {
+    pub config: WebAuthnConfig,
+    pub extension: PasskeyExtension,
+}
+
+
+impl WebAuthnVerifier {
+
+    pub fn new(config: WebAuthnConfig, extension: PasskeyExtension) -> Self {
+        Self { config, extension }
+    }
+
+    /// Takes the authenticator and client data from the `extension` of the payload
+    pub fn from_payload(config: WebAuthnConfig, payload: &AuthPayload) -> StdResult<Self> {
+        let extension = payload.extension
+            .as_ref()
+            .ok_or_else(|| StdError::msg("WebAuthn verification requires `extension` in the payload"))?;
+        Ok(Self::new(config, from_json(extension)?))
+    }
+
+    /// Checks the client data and the authenticator data against the data and the relying party settings
+    pub fn check_assertion(&self, data: &[u8]) -> StdResult<()> {
+        let client_data : ClientData = from_json(&self.extension.client_data_json)?;
+
+        if client_data.ty != "webauthn.get" {
+            return Err(StdError::msg(format!("Invalid client data type: {}", client_data.ty)));
+        }
+        if client_data.challenge.trim_end_matches('=') != base64url(data) {
+            return Err(StdError::msg("Challenge doesn't match the data"));
+        }
+        if client_data.origin != self.config.origin {
+            return Err(StdError::msg(format!("Invalid origin: {}", client_data.origin)));
+        }
+        // assertions made inside an iframe of another origin could be requested by any website
+        if client_data.cross_origin == Some(true) {
+            return Err(StdError::msg("Cross-origin assertions aren't accepted"));
+        }
+
+        let auth_data = self.extension.authenticator_data.as_slice();
+        if auth_data.len() < MIN_AUTH_DATA_LEN {
+            return Err(StdError::msg("Authenticator data is too short"));
+        }
+        if auth_data[..32] != Sha256::digest(self.config.rp_id.as_bytes())[..] {
+            return Err(StdError::msg("Authenticator data is for another relying party"));
+        }
+        let flags = auth_data[32];
+        if flags & FLAG_USER_PRESENT == 0 {
+            return Err(StdError::msg("User presence flag isn't set"));
+        }
+        if self.config.user_verification && flags & FLAG_USER_VERIFIED == 0 {
+            return Err(StdError::msg("User verification flag isn't set"));
+        }
+        Ok(())
+    }
+}
+
+
+impl SignatureVerifier for WebAuthnVerifier {
+    fn verify(&self, api: &dyn Api, data: &[u8], signature: &[u8], pubkey: &[u8]) -> StdResult<bool> {
+        self.check_assertion(data)?;
+
+        let client_data_hash = Sha256::digest(&self.extension.client_data_json);
+        let hash : [u8; 32] = Sha256::new()
+            .chain_update(&self.extension.authenticator_data)
+            .chain_update(client_data_hash)
+            .finalize()
+            .into();
+
+        let signature = match signature.len() {
+            64 => signature.to_vec(),
+            _ => der_to_raw_signature(signature)?,
+        };
+        Ok(api.secp256r1_verify(&hash, &signature, pubkey)?)
+    }
+}
+
+
+/// Base64url encoding without padding that authenticators use for the challenges
+pub fn base64url(data: &[u8]) -> String {
+    Binary::from(data)
+        .to_base64()
+        .trim_end_matches('=')
+        .replace('+', "-")
+        .replace('/', "_")
+}
+
+
+/// Converts an ASN.1 DER encoded ECDSA signature into 64 bytes of `r || s`
+fn der_to_raw_signature(der: &[u8]) -> StdResult<Vec<u8>> {
+    let invalid = || StdError::msg("Invalid DER signature");
+
+    if der.len() < 8 || der[0] != 0x30 || der[1] as usize != der.len() - 2 {
+        return Err(invalid());
+    }
+    let mut raw = vec![0u8; 64];
+    let mut rest = &der[2..];
+
+    for chunk in raw.chunks_mut(32) {
+        if rest.len() < 2 || rest[0] != 0x02 {
+            return Err(invalid());
+        }
+        let len = rest[1] as usize;
+        if rest.len() < 2 + len {
+            return Err(invalid());
+        }
+        // integers are signed so they might have a leading zero
+        let int = &rest[2..2 + len];
+        let int = &int[int.iter().take_while(|b| **b == 0).count()..];
+        if int.len() > 32 {
+            return Err(invalid());
+        }
+        chunk[32 - int.len()..].copy_from_slice(int);
+        rest = &rest[2 + len..];
+    }
+
+    if !rest.is_empty() {
+        return Err(invalid());
+    }
+    Ok(raw)
+}
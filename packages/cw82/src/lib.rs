@@ -2,7 +2,8 @@ use cosmwasm_schema::{cw_serde, QueryResponses};
 use types::wasm::{Binary, CosmosMsg, Empty};
 
 
-pub const INTERFACE_NAME : &str = "crates.io:cw82";
+pub const INTERFACE_NAME : &str = "crates:cw82";
+pub const INTERFACE_VERSION: &str = env!("CARGO_PKG_VERSION");
 #[cfg(feature = "multi")]
 pub const INTERFACE_NAME_MULTI: &str = "crates:cw82-multi";
 
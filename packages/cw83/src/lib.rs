@@ -2,6 +2,7 @@ mod msg;
 
 pub const CREATE_ACCOUNT_REPLY_ID : u64 = 82;
 pub const INTERFACE_NAME: &str = "crates:cw83";
+pub const INTERFACE_VERSION: &str = env!("CARGO_PKG_VERSION");
 #[cfg(feature = "multi")]
 pub const INTERFACE_NAME_MULTI: &str = "crates:cw83-multi";
 
@@ -1,6 +1,7 @@
 mod msg;
 
 pub const INTERFACE_NAME: &str = "crates:cw84";
+pub const INTERFACE_VERSION: &str = env!("CARGO_PKG_VERSION");
 #[cfg(feature = "multi")]
 pub const INTERFACE_NAME_MULTI: &str = "crates:cw84-multi";
 
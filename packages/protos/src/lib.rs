@@ -274,6 +274,98 @@ pub fn valid_signature_one(metadata: TokenStream, input: TokenStream) -> TokenSt
 
 
 
+/// Procedural macro to extend an enum with a query variant explaining the result of a signature validation.
+///
+/// This macro inserts a `ValidSignatureDetailed` variant that takes the same arguments as `ValidSignature`
+/// but returns `ValidSignatureDetailedResponse` with the reason of a failure and the matched credential.
+/// It is meant to be used together with `valid_signature_query` and supports an optional custom payload
+/// type to override the default `Binary` type for the `payload` field, which remains wrapped in `Option`.
+///
+/// # Arguments
+///
+/// The macro accepts **zero or one type argument** via the attribute:
+/// - **Payload type** (optional): The type for the `payload` field (e.g., `AuthPayload`), wrapped in
+///   `Option`. Defaults to `Option<cosmwasm_std::Binary>` if not provided.
+///
+/// # Generated Variants
+///
+/// The macro inserts the following query variant:
+/// - `ValidSignatureDetailed`: Verifies a single signature and explains why it was rejected.
+///
+/// # Notes
+/// - The `#[valid_signature_detailed_query]` attribute must be applied **before** `#[cw_serde]` or
+///   other derive macros.
+/// - The enum must derive `#[derive(QueryResponses)]` from `cosmwasm_schema` to support the
+///   `#[returns(...)]` attribute used in the generated variant.
+/// - The response can be built from the result of `SignatureVerifier::check` with
+///   `ValidSignatureDetailedResponse::from_result`.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use cw81::{valid_signature_query, valid_signature_detailed_query, AuthPayload};
+/// use cosmwasm_schema::{cw_serde, QueryResponses};
+///
+/// #[valid_signature_query(AuthPayload)]
+/// #[valid_signature_detailed_query(AuthPayload)]
+/// #[cw_serde]
+/// #[derive(QueryResponses)]
+/// pub enum QueryMsg {
+///     // User-defined queries
+/// }
+///
+/// // Generated in addition to the `ValidSignature` variant:
+/// // pub enum QueryMsg {
+/// //     // User-defined queries
+/// //
+/// //     #[returns(::cw81::ValidSignatureDetailedResponse)]
+/// //     ValidSignatureDetailed {
+/// //         data: ::cosmwasm_std::Binary,
+/// //         signature: ::cosmwasm_std::Binary,
+/// //         payload: Option<AuthPayload>,
+/// //     },
+/// // }
+/// ```
+///
+/// # Errors
+///
+/// - Fails with a compile-time error if the attribute argument is invalid (e.g., not a type path or
+///   more than one argument).
+/// - Fails if the input is not a valid enum or if the merge with generated variants cannot be
+///   performed.
+///
+/// This macro is an optional extension of the CW81 specification for signature validation.
+#[proc_macro_attribute]
+pub fn valid_signature_detailed_query(metadata: TokenStream, input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(metadata as AttributeArgs);
+
+    let payload_type = match args.len() {
+        0 => quote!(Option<::cosmwasm_std::Binary>),
+        1 => match &args[0] {
+            NestedMeta::Meta(syn::Meta::Path(path)) => quote!(Option<#path>),
+            other => return comp_err!(other, "Expected a type for the payload, like `CustomPayload`"),
+        },
+        _ => return comp_err!(&args[1], "Expected at most 1 argument"),
+    };
+
+    merge_variants(
+        input,
+        quote! {
+            enum Right {
+                #[returns(::cw81::ValidSignatureDetailedResponse)]
+                ValidSignatureDetailed {
+                    data: ::cosmwasm_std::Binary,
+                    signature: ::cosmwasm_std::Binary,
+                    payload: #payload_type
+                },
+            }
+        }
+        .into(),
+    )
+}
+
+
+
 /// Procedural macro to extend an enum with standardized smart account query variants.
 ///
 /// This macro checks whether the input enum is generic over `T` and inserts variants accordingly. If
@@ -749,6 +841,257 @@ pub fn account_execute(_metadata: TokenStream, input: TokenStream) -> TokenStrea
 }
 
 
+/// Procedural macro to extend an enum with standardized supported interface query variants.
+///
+/// This macro inserts the variants of `Cw22QueryMsg` into a `QueryMsg` enum so that callers can
+/// introspect the interfaces declared by a contract with smart queries instead of raw storage
+/// lookups. That is useful for contracts with a custom storage layout and for environments
+/// where raw queries are not available.
+///
+/// # Arguments
+///
+/// The macro does not accept any arguments via the attribute.
+///
+/// # Generated Variants
+///
+/// The macro inserts the following query variants:
+/// - `SupportedInterface`: Returns the version of a single interface if it is supported.
+/// - `SupportedInterfaces`: Returns a paginated list of all supported interfaces.
+/// - `Supports`: Checks whether an interface is supported with a version matching a SemVer requirement.
+///
+/// # Notes
+/// - The `#[supported_interface_query]` attribute must be applied **before** `#[cw_serde]` or other
+///   derive macros.
+/// - The enum must derive `#[derive(QueryResponses)]` from `cosmwasm_schema` to support the
+///   `#[returns(...)]` attributes used in the generated variants.
+/// - The variants can be answered with the handlers from `cw22` such as `query_supported_interface`,
+///   `query_supported_interfaces` and `query_supports`.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use cw22::supported_interface_query;
+/// use cosmwasm_schema::{cw_serde, QueryResponses};
+///
+/// #[supported_interface_query]
+/// #[cw_serde]
+/// #[derive(QueryResponses)]
+/// pub enum QueryMsg {
+///     // User-defined queries
+/// }
+///
+/// // Generated:
+/// // pub enum QueryMsg {
+/// //     // User-defined queries
+/// //
+/// //     #[returns(::cw22::SupportedInterfaceResponse)]
+/// //     SupportedInterface {
+/// //         name: String,
+/// //     },
+/// //
+/// //     #[returns(::cw22::SupportedInterfacesResponse)]
+/// //     SupportedInterfaces {
+/// //         start_after: Option<String>,
+/// //         limit: Option<u32>,
+/// //     },
+/// //
+/// //     #[returns(::cw22::SupportsInterfaceResponse)]
+/// //     Supports {
+/// //         name: String,
+/// //         version_req: String,
+/// //     },
+/// // }
+/// ```
+///
+/// # Errors
+///
+/// - Fails with a compile-time error if any argument is passed to the attribute.
+/// - Fails if the input is not a valid enum or if the merge with generated variants cannot be
+///   performed.
+///
+/// This macro is part of the CW22 specification for supported interfaces.
+#[proc_macro_attribute]
+pub fn supported_interface_query(metadata: TokenStream, input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(metadata as AttributeArgs);
+    if let Some(arg) = args.first() {
+        return comp_err!(arg, "Expected no arguments");
+    }
+
+    merge_variants(
+        input,
+        quote! {
+            enum Right {
+                #[returns(::cw22::SupportedInterfaceResponse)]
+                SupportedInterface {
+                    name: String,
+                },
+                #[returns(::cw22::SupportedInterfacesResponse)]
+                SupportedInterfaces {
+                    start_after: Option<String>,
+                    limit: Option<u32>,
+                },
+                #[returns(::cw22::SupportsInterfaceResponse)]
+                Supports {
+                    name: String,
+                    version_req: String,
+                },
+            }
+        }
+        .into(),
+    )
+}
+
+
+/// Procedural macro to declare the interfaces implemented by a contract in one place.
+///
+/// This macro leaves the annotated item untouched and generates a `register_interfaces` function
+/// that stores every declared interface with `cw22::set_contract_supported_interface`. The names and
+/// versions are taken from the `INTERFACE_NAME` and `INTERFACE_VERSION` constants of the corresponding
+/// packages, so the declared interfaces can't drift from the packages the messages are injected from.
+///
+/// # Arguments
+///
+/// The macro accepts a comma separated list of:
+/// - **Package name** (e.g. `cw81`): declares the interface of the package with its crate version.
+/// - **Package name with a version** (e.g. `cw82 = "2.2.0"`): declares the interface of the package
+///   with an explicit version.
+/// - **`multi`** flag: additionally declares the `-multi` variants of every listed package that
+///   defines them (`cw81`, `cw82`, `cw83` and `cw84`). The packages must have the `multi` feature enabled.
+///
+/// `cw1` doesn't export the constants and is resolved through `cw22::KnownInterface::Cw1` instead.
+///
+/// # Generated Items
+///
+/// - `register_interfaces(storage)`: should be called in `instantiate` and after every migration.
+///   The function is generated next to the annotated item, e.g. next to a message enum or a
+///   module declaration. The storage is taken through `cw22::wasm`, so it matches the cosmwasm
+///   version of `cw22` rather than the one of the contract.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use cw22::supports;
+/// use cosmwasm_schema::{cw_serde, QueryResponses};
+///
+/// #[supports(cw81, cw82 = "2.2.0", cw22, multi)]
+/// #[cw_serde]
+/// #[derive(QueryResponses)]
+/// pub enum QueryMsg {
+///     // User-defined queries
+/// }
+///
+/// // Generated:
+/// // pub fn register_interfaces(
+/// //     storage: &mut dyn ::cw22::wasm::Storage
+/// // ) -> Result<(), ::cw22::Cw22Error> {
+/// //     ::cw22::set_contract_supported_interface(storage, &[
+/// //         (::cw81::INTERFACE_NAME, ::cw81::INTERFACE_VERSION),
+/// //         (::cw81::INTERFACE_NAME_MULTI, ::cw81::INTERFACE_VERSION),
+/// //         (::cw82::INTERFACE_NAME, "2.2.0"),
+/// //         (::cw82::INTERFACE_NAME_MULTI, "2.2.0"),
+/// //         (::cw22::INTERFACE_NAME, ::cw22::INTERFACE_VERSION),
+/// //     ] /* as ContractSupportedInterface */)
+/// // }
+/// ```
+///
+/// # Errors
+///
+/// - Fails with a compile-time error if an argument isn't a package name, a package name with a string
+///   version or the `multi` flag, or if no packages are listed.
+/// - Fails with a compile-time error if an explicit version isn't a valid SemVer version.
+/// - Fails with a compile-time error if a listed package is not a dependency or doesn't export the constants.
+///
+/// This macro is part of the CW22 specification for supported interfaces.
+#[proc_macro_attribute]
+pub fn supports(metadata: TokenStream, input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(metadata as AttributeArgs);
+
+    let mut multi = false;
+    let mut packages : Vec<(syn::Ident, Option<syn::LitStr>)> = Vec::with_capacity(args.len());
+
+    for arg in args.iter() {
+        match arg {
+            NestedMeta::Meta(syn::Meta::Path(path)) => match path.get_ident() {
+                Some(ident) if ident == "multi" => multi = true,
+                Some(ident) => packages.push((ident.clone(), None)),
+                None => return comp_err!(path, "Expected a package name, like `cw81`"),
+            },
+            NestedMeta::Meta(syn::Meta::NameValue(nv)) => match (nv.path.get_ident(), &nv.lit) {
+                (Some(ident), syn::Lit::Str(version)) => {
+                    if semver::Version::parse(&version.value()).is_err() {
+                        return comp_err!(version, "Expected a SemVer version, like \"2.2.0\"");
+                    }
+                    packages.push((ident.clone(), Some(version.clone())))
+                },
+                (None, _) => return comp_err!(&nv.path, "Expected a package name, like `cw81`"),
+                (_, other) => return comp_err!(other, "Expected a version string, like \"2.2.0\""),
+            },
+            other => return comp_err!(other, "Expected a package name, like `cw81` or `cw82 = \"2.2.0\"`"),
+        }
+    }
+
+    if packages.is_empty() {
+        return syn::Error::new(
+            proc_macro2::Span::call_site(),
+            "Expected at least one package name, like `#[supports(cw81)]`"
+        )
+        .to_compile_error()
+        .into();
+    }
+
+    let interfaces = packages.iter().flat_map(|(ident, version)| {
+        let (name, name_multi, default_version) = if ident == "cw1" {
+            (
+                quote!(::cw22::KnownInterface::Cw1.name()),
+                None,
+                quote!(::cw22::KnownInterface::Cw1.version()),
+            )
+        } else {
+            let has_multi = ["cw81", "cw82", "cw83", "cw84"].iter().any(|p| ident == p);
+            (
+                quote!(::#ident::INTERFACE_NAME),
+                has_multi.then(|| quote!(::#ident::INTERFACE_NAME_MULTI)),
+                quote!(::#ident::INTERFACE_VERSION),
+            )
+        };
+        let version = version
+            .as_ref()
+            .map(|v| v.to_token_stream())
+            .unwrap_or(default_version);
+
+        std::iter::once(name)
+            .chain(name_multi.filter(|_| multi))
+            .map(move |name| quote! {
+                ::cw22::ContractSupportedInterface {
+                    supported_interface: #name.into(),
+                    version: #version.into(),
+                }
+            })
+            .collect::<Vec<TokenStream2>>()
+    });
+
+    let register = quote! {
+        /// Stores the interfaces declared with `#[supports(...)]`. Should be called in `instantiate`
+        /// and after every migration.
+        pub fn register_interfaces(
+            storage: &mut dyn ::cw22::wasm::Storage
+        ) -> Result<(), ::cw22::Cw22Error> {
+            ::cw22::set_contract_supported_interface(storage, &[
+                #(#interfaces),*
+            ])
+        }
+    };
+
+    let item = TokenStream2::from(input);
+
+    quote! {
+        #item
+        #register
+    }
+    .into()
+}
+
+
 /// Procedural macro to extend an enum with a standardized `CreateAccount` execute variant
 /// for registry-based smart accounts.
 ///
@@ -1407,7 +1750,7 @@ pub fn signed_query_multi(metadata: TokenStream, input: TokenStream) -> TokenStr
             },
             other => return comp_err!(other, "Expected a type for the inner action message, like `ExecuteMsg`"),
         },
-        _ => return comp_err!(&args.get(0), "Expected one to four arguments: `#[signed_query(ExecuteMsg[, SignedDataMsg][, CustomPayload][, CustomMultiPayload])]`"),
+        _ => return comp_err!(&args.first(), "Expected one to four arguments: `#[signed_query(ExecuteMsg[, SignedDataMsg][, CustomPayload][, CustomMultiPayload])]`"),
     };
 
     let right_enum = quote! {
@@ -1559,7 +1902,7 @@ pub fn signed_query_one(metadata: TokenStream, input: TokenStream) -> TokenStrea
             ),
             other => return comp_err!(other, "Expected a type for the inner action message, like `ExecuteMsg`"),
         },
-        _ => return comp_err!(&args.get(0), "Expected one to three arguments: `#[signed_query(ExecuteMsg[, SignedDataMsg][, CustomPayload])]`"),
+        _ => return comp_err!(&args.first(), "Expected one to three arguments: `#[signed_query(ExecuteMsg[, SignedDataMsg][, CustomPayload])]`"),
     };
 
     let right_enum = quote! {
@@ -1797,7 +2140,7 @@ pub fn signed_execute_multi(metadata: TokenStream, input: TokenStream) -> TokenS
         _ => return comp_err!(&args[1], "Expected at most 2 arguments"),
     };
 
-    let right_enum = if args.len() > 0 {
+    let right_enum = if !args.is_empty() {
         // Include Native variant when exec_type is explicitly specified
         quote! {
             enum Right {
@@ -47,7 +47,7 @@ pub (crate) fn merge_variants(left: TokenStream, right: TokenStream) -> TokenStr
     };
 
     // insert variants from the right to the left
-    variants.extend(to_add.into_iter());
+    variants.extend(to_add);
 
     quote! { #left }.into()
 }
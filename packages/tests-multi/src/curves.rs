@@ -1,4 +1,4 @@
-use types::wasm::{Binary, to_json_binary};
+use types::wasm::{Binary, to_json_binary, testing::MockApi};
 use cosmwasm_crypto::{secp256k1_verify};
 
 use k256::{
@@ -19,7 +19,14 @@ use sha2::{
     digest::{Update, Digest}
 };
 
-use cw81::Cw81QueryMsg;
+use cw81::{
+    adr036_sign_doc, pubkey_to_address, Adr036Verifier, AuthPayload,
+    eip191_hash, eth_address, hash_struct, keccak256, parse_eth_address,
+    Eip191Verifier, Eip712Domain, Eip712Verifier,
+    base64url, PasskeyExtension, WebAuthnConfig, WebAuthnVerifier,
+    HashExtension, HashMode,
+    Cw81QueryMsg, Ed25519Verifier, KeyType, SignatureVerifier
+};
 
 
 const MSG: &str = "Testing String!";
@@ -37,7 +44,7 @@ fn simple_secp256k1() {
     let public_key = VerifyingKey::from(&secret_key);
 
     let msg = Cw81QueryMsg::ValidSignature { 
-        data: data, 
+        data, 
         signature: signature.to_bytes().as_slice().into(), 
         payload: Some(public_key.to_encoded_point(false).as_bytes().into()) 
     };
@@ -73,18 +80,16 @@ fn simple_secp256k1() {
 fn batch_ed25519() {
 
     let msg : Binary = to_json_binary(&MSG).unwrap();
-    let msg_digest = Sha256::new().chain(&msg).finalize();
 
     let secret_key = Ed25519SigningKey::new(OsRng);
     let pub_key_bytes : [u8; 32] = Ed25519VerificationKey::from(&secret_key).into();
     let pub_key_binary : Binary = pub_key_bytes.into();
     
-    let signature = secret_key.sign(&msg_digest);
+    let signature = secret_key.sign(&msg);
     let signature : Binary = signature.to_bytes().as_slice().into();
     
     let another_msg : Binary = to_json_binary("another msg").unwrap();
-    let another_digest = Sha256::new().chain(&another_msg).finalize();
-    let another_signature : Binary = secret_key.sign(&another_digest).to_bytes().as_slice().into();
+    let another_signature : Binary = secret_key.sign(&another_msg).to_bytes().as_slice().into();
 
     let data = vec![msg, another_msg];
     let signatures = vec![signature, another_signature];
@@ -104,42 +109,367 @@ fn batch_ed25519() {
             payload 
         } => {
 
-            let hashes : Vec<Vec<u8>> = data
-                .iter()
-                .map(|d|  Sha256::new().chain(&d).finalize().to_vec())
-                .collect();
-
-            let hashes : Vec<&[u8]> = hashes
-                .iter()
-                .map(|h| h.as_slice())
-                .collect();
-
-            let _signatures : Vec<&[u8]> = signatures
-                .iter()
-                .map(|sig|sig.as_slice())
-                .collect();
-
+            let api = MockApi::default();
             let public_key = payload.unwrap();
 
-            let keys_count = hashes.len();
-            let mut keys: Vec<&[u8]> = Vec::with_capacity(keys_count);
+            assert_eq!(
+                Ed25519Verifier.verify_batch(&api, &data, &signatures, &public_key).unwrap(),
+                vec![true, true]
+            );
 
-            for _ in 0..keys_count {
-                keys.push(public_key.as_slice());
-            }
+            // one of the signatures is for another message
+            let swapped = vec![signatures[0].clone(), signatures[0].clone()];
+            assert_eq!(
+                Ed25519Verifier.verify_batch(&api, &data, &swapped, &public_key).unwrap(),
+                vec![true, false]
+            );
 
-            // assert!(
-            //     ed25519_batch_verify(
-            //         &hashes,
-            //         &signatures,
-            //         &keys
-            //     ).unwrap()
-            // ); 
+            // lengths must match
+            assert!(Ed25519Verifier.verify_batch(&api, &data, &signatures[..1], &public_key).is_err());
         },
 
-        _ => {  }
+        _ => { /* QueryMsg::ValidSignature */ }
     }
 
     
 }
 
+
+#[test]
+fn verifiers_by_key_type() {
+    use p256::ecdsa::{
+        signature::DigestSigner as _,
+        SigningKey as P256SigningKey, 
+        Signature as P256Signature
+    };
+
+    let api = MockApi::default();
+    let data : Binary = to_json_binary(&MSG).unwrap();
+    let data_digest = Sha256::new().chain(&data);
+
+    // secp256k1
+    let secret_key = SigningKey::random(&mut OsRng);
+    let signature: Signature = secret_key.sign_digest(data_digest.clone());
+    let public_key = VerifyingKey::from(&secret_key).to_encoded_point(false);
+
+    assert!(KeyType::Secp256k1.is_valid(&api, &data, &signature.to_bytes(), public_key.as_bytes()));
+    assert!(!KeyType::Secp256k1.is_valid(&api, b"another", &signature.to_bytes(), public_key.as_bytes()));
+    // malformed signature is an error rather than a failed verification
+    assert!(KeyType::Secp256k1.verify(&api, &data, b"signature", public_key.as_bytes()).is_err());
+
+    // secp256r1 (passkeys)
+    let secret_key = P256SigningKey::random(&mut OsRng);
+    let signature: P256Signature = secret_key.sign_digest(data_digest);
+    let public_key = secret_key.verifying_key().to_encoded_point(false);
+
+    assert!(KeyType::Secp256r1.is_valid(&api, &data, &signature.to_bytes(), public_key.as_bytes()));
+    assert!(!KeyType::Secp256k1.is_valid(&api, &data, &signature.to_bytes(), public_key.as_bytes()));
+
+    // ed25519
+    let secret_key = Ed25519SigningKey::new(OsRng);
+    let public_key : [u8; 32] = Ed25519VerificationKey::from(&secret_key).into();
+    let signature = secret_key.sign(&data);
+
+    assert!(KeyType::Ed25519.is_valid(&api, &data, &signature.to_bytes(), &public_key));
+
+    // signatures of a digest are only accepted when the hashing is requested explicitly
+    let digest_signature = secret_key.sign(&Sha256::new().chain(&data).finalize());
+    assert!(!KeyType::Ed25519.is_valid(&api, &data, &digest_signature.to_bytes(), &public_key));
+    assert!(KeyType::Ed25519.with_hash_mode(HashMode::Sha256).is_valid(&api, &data, &digest_signature.to_bytes(), &public_key));
+}
+
+
+#[test]
+fn adr036_sign_arbitrary() {
+    use types::wasm::from_base64;
+
+    // vector from cosmjs
+    let public_key = from_base64("A08EGB7ro1ORuFhjOnZcSgwYlpe0DSFjVNUIkNNQxwKQ").unwrap();
+    assert_eq!(
+        pubkey_to_address(&public_key, "cosmos").unwrap(),
+        "cosmos1pkptre7fdkl6gfrzlesjjvhxhlc3r4gmmk8rs6"
+    );
+
+    let doc = adr036_sign_doc(MSG.as_bytes(), "cosmos1pkptre7fdkl6gfrzlesjjvhxhlc3r4gmmk8rs6").unwrap();
+    assert_eq!(
+        String::from_utf8(doc).unwrap(),
+        concat!(
+            r#"{"account_number":"0","chain_id":"","fee":{"amount":[],"gas":"0"},"memo":"","#,
+            r#""msgs":[{"type":"sign/MsgSignData","value":{"data":"VGVzdGluZyBTdHJpbmch","#,
+            r#""signer":"cosmos1pkptre7fdkl6gfrzlesjjvhxhlc3r4gmmk8rs6"}}],"sequence":"0"}"#
+        )
+    );
+
+    // wallet signs the sign doc with its own address
+    let api = MockApi::default();
+    let secret_key = SigningKey::random(&mut OsRng);
+    let compressed = VerifyingKey::from(&secret_key).to_encoded_point(true);
+    let uncompressed = VerifyingKey::from(&secret_key).to_encoded_point(false);
+
+    let verifier = Adr036Verifier::from_payload(&AuthPayload {
+        hrp: Some("stars".into()),
+        credential_id: None,
+        extension: None,
+        delegation_depth: None,
+    }).unwrap();
+
+    let doc = verifier.sign_doc(MSG.as_bytes(), compressed.as_bytes()).unwrap();
+    let signature: Signature = secret_key.sign_digest(Sha256::new().chain(&doc));
+
+    assert!(verifier.is_valid(&api, MSG.as_bytes(), &signature.to_bytes(), compressed.as_bytes()));
+    assert!(verifier.is_valid(&api, MSG.as_bytes(), &signature.to_bytes(), uncompressed.as_bytes()));
+
+    // address with another prefix is a different signer
+    assert!(!Adr036Verifier::new("cosmos").is_valid(&api, MSG.as_bytes(), &signature.to_bytes(), compressed.as_bytes()));
+    // not a direct signature of the data
+    assert!(!KeyType::Secp256k1.is_valid(&api, MSG.as_bytes(), &signature.to_bytes(), compressed.as_bytes()));
+
+    // payload without prefix
+    assert!(Adr036Verifier::from_payload(&AuthPayload {
+        hrp: None,
+        credential_id: None,
+        extension: None,
+        delegation_depth: None,
+    }).is_err());
+}
+
+
+#[test]
+fn eip191_personal_sign() {
+    let api = MockApi::default();
+    let secret_key = SigningKey::random(&mut OsRng);
+    let public_key = VerifyingKey::from(&secret_key).to_encoded_point(false);
+    let address = eth_address(public_key.as_bytes()).unwrap();
+
+    let (signature, recovery_id) = secret_key
+        .sign_prehash_recoverable(&eip191_hash(MSG.as_bytes()))
+        .unwrap();
+
+    // wallets add 27 to the recovery id
+    let mut eth_signature = signature.to_vec();
+    eth_signature.push(recovery_id.to_byte() + 27);
+
+    assert!(Eip191Verifier.is_valid(&api, MSG.as_bytes(), &eth_signature, &address));
+    assert!(KeyType::Ethereum.is_valid(&api, MSG.as_bytes(), &eth_signature, public_key.as_bytes()));
+    assert!(!KeyType::Ethereum.is_valid(&api, b"another", &eth_signature, &address));
+
+    let results = KeyType::Ethereum.verify_batch(
+        &api,
+        &[Binary::from(MSG.as_bytes()), Binary::from(b"another")],
+        &[eth_signature.clone().into(), eth_signature.clone().into()],
+        &address
+    ).unwrap();
+    assert_eq!(results, vec![true, false]);
+
+    // signature without the recovery id
+    assert!(Eip191Verifier.verify(&api, MSG.as_bytes(), &eth_signature[..64], &address).is_err());
+}
+
+
+#[test]
+fn eip712_typed_data() {
+    use types::wasm::from_hex;
+
+    // example from the EIP-712 specification
+    let domain = Eip712Domain {
+        name: Some("Ether Mail".into()),
+        version: Some("1".into()),
+        chain_id: Some(1),
+        verifying_contract: Some("0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC".into()),
+        salt: None,
+    };
+    assert_eq!(
+        domain.separator().unwrap().to_vec(),
+        from_hex("f2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f").unwrap()
+    );
+
+    let person = |name: &str, wallet: &str| {
+        let mut address = [0u8; 32];
+        address[12..].copy_from_slice(&parse_eth_address(wallet).unwrap());
+        hash_struct("Person(string name,address wallet)", &[keccak256(name.as_bytes()), address])
+    };
+    let mail = hash_struct(
+        "Mail(Person from,Person to,string contents)Person(string name,address wallet)",
+        &[
+            person("Cow", "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"),
+            person("Bob", "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB"),
+            keccak256(b"Hello, Bob!"),
+        ]
+    );
+    assert_eq!(
+        mail.to_vec(),
+        from_hex("c52c0ee5d84264471806290a3f2c4cecfc5490626bf912d01f240d7a274b371e").unwrap()
+    );
+
+    let signature = from_hex(concat!(
+        "4355c47d63924e8a72e509b65029052eb6c299d53a04e167c5775fd466751c9d",
+        "07299936d304c153f6443dfa05f40ff007d72911b6f72307f996231605b91562",
+        "1c"
+    )).unwrap();
+    let cow = parse_eth_address("0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826").unwrap();
+
+    let api = MockApi::default();
+    let verifier = Eip712Verifier::new(domain.clone());
+    assert!(verifier.is_valid(&api, &mail, &signature, &cow));
+
+    // another chain
+    let verifier = Eip712Verifier::new(Eip712Domain { chain_id: Some(2), ..domain });
+    assert!(!verifier.is_valid(&api, &mail, &signature, &cow));
+    // data must be a hash of the struct
+    assert!(verifier.verify(&api, MSG.as_bytes(), &signature, &cow).is_err());
+}
+
+
+#[test]
+fn webauthn_assertions() {
+    use p256::ecdsa::{signature::Signer, SigningKey as P256SigningKey, Signature as P256Signature};
+
+    let api = MockApi::default();
+    let secret_key = P256SigningKey::random(&mut OsRng);
+    let public_key = secret_key.verifying_key().to_encoded_point(true);
+    let data = MSG.as_bytes();
+
+    let mut authenticator_data = Sha256::digest(b"localhost").to_vec();
+    authenticator_data.extend_from_slice(&[0x01, 0, 0, 0, 0]);
+    let client_data_json = format!(
+        r#"{{"type":"webauthn.get","challenge":"{}","origin":"http://localhost:3000"}}"#,
+        base64url(data)
+    );
+    let mut signed = authenticator_data.clone();
+    signed.extend_from_slice(&Sha256::digest(client_data_json.as_bytes()));
+    let signature: P256Signature = secret_key.sign(&signed);
+
+    let extension = PasskeyExtension {
+        authenticator_data: authenticator_data.clone().into(),
+        client_data_json: client_data_json.into_bytes().into(),
+    };
+    let config = WebAuthnConfig {
+        origin: "http://localhost:3000".into(),
+        rp_id: "localhost".into(),
+        user_verification: false,
+    };
+
+    let verifier = WebAuthnVerifier::from_payload(config.clone(), &AuthPayload {
+        hrp: None,
+        credential_id: None,
+        extension: Some(to_json_binary(&extension).unwrap()),
+        delegation_depth: None,
+    }).unwrap();
+
+    // both raw and DER encoded signatures are accepted
+    assert!(verifier.verify(&api, data, &signature.to_bytes(), public_key.as_bytes()).unwrap());
+    assert!(verifier.verify(&api, data, signature.to_der().as_bytes(), public_key.as_bytes()).unwrap());
+
+    // challenge is for another message
+    assert!(verifier.verify(&api, b"another", &signature.to_bytes(), public_key.as_bytes()).is_err());
+
+    // another relying party
+    let verifier = WebAuthnVerifier::new(
+        WebAuthnConfig { rp_id: "example.com".into(), ..config.clone() },
+        extension.clone()
+    );
+    assert!(!verifier.is_valid(&api, data, &signature.to_bytes(), public_key.as_bytes()));
+
+    // another origin
+    let verifier = WebAuthnVerifier::new(
+        WebAuthnConfig { origin: "https://example.com".into(), ..config.clone() },
+        extension.clone()
+    );
+    assert!(!verifier.is_valid(&api, data, &signature.to_bytes(), public_key.as_bytes()));
+
+    // user verification is required
+    let verifier = WebAuthnVerifier::new(WebAuthnConfig { user_verification: true, ..config.clone() }, extension);
+    assert!(!verifier.is_valid(&api, data, &signature.to_bytes(), public_key.as_bytes()));
+
+    // assertion requested from an iframe of another website
+    let client_data_json = format!(
+        r#"{{"type":"webauthn.get","challenge":"{}","origin":"http://localhost:3000","crossOrigin":true}}"#,
+        base64url(data)
+    );
+    let mut signed = authenticator_data.clone();
+    signed.extend_from_slice(&Sha256::digest(client_data_json.as_bytes()));
+    let signature: P256Signature = secret_key.sign(&signed);
+    let verifier = WebAuthnVerifier::new(config, PasskeyExtension {
+        authenticator_data: authenticator_data.into(),
+        client_data_json: client_data_json.into_bytes().into(),
+    });
+    assert!(!verifier.is_valid(&api, data, &signature.to_bytes(), public_key.as_bytes()));
+}
+
+
+#[test]
+fn hash_modes() {
+    use types::wasm::from_hex;
+
+    let api = MockApi::default();
+    let data = MSG.as_bytes();
+
+    assert_eq!(
+        HashMode::BitcoinMessage.hash(data),
+        from_hex("12cfc4f3f84d8985f4f2ed245ca7f6889bf06c0ae6afb252c512010724b507cd").unwrap()
+    );
+    assert_eq!(HashMode::Prehashed.hash(data), data.to_vec());
+    assert_eq!(HashMode::Sha512.hash(data).len(), 64);
+
+    let secret_key = SigningKey::random(&mut OsRng);
+    let public_key = VerifyingKey::from(&secret_key).to_encoded_point(true);
+
+    for mode in [HashMode::Sha256, HashMode::Keccak256, HashMode::BitcoinMessage] {
+        let (signature, recovery_id) = secret_key.sign_prehash_recoverable(&mode.hash(data)).unwrap();
+        let verifier = KeyType::Secp256k1.with_hash_mode(mode);
+        assert!(verifier.is_valid(&api, data, &signature.to_bytes(), public_key.as_bytes()));
+
+        let results = verifier.verify_batch(
+            &api,
+            &[Binary::from(data), Binary::from(b"another")],
+            &[signature.to_vec().into(), signature.to_vec().into()],
+            public_key.as_bytes(),
+        ).unwrap();
+        assert_eq!(results, vec![true, false]);
+
+        if mode == HashMode::BitcoinMessage {
+            // compact signatures of bitcoin wallets start with a recovery header
+            let compact = [&[31 + recovery_id.to_byte()], signature.to_bytes().as_slice()].concat();
+            assert!(verifier.is_valid(&api, data, &compact, public_key.as_bytes()));
+        } else {
+            assert!(!KeyType::Secp256k1.with_hash_mode(HashMode::BitcoinMessage)
+                .is_valid(&api, data, &signature.to_bytes(), public_key.as_bytes()));
+        }
+    }
+
+    // ethereum signatures keep the recovery id at the end
+    let (signature, recovery_id) = secret_key.sign_prehash_recoverable(&HashMode::BitcoinMessage.hash(data)).unwrap();
+    let eth_signature = [signature.to_bytes().as_slice(), &[recovery_id.to_byte() + 27]].concat();
+    let address = eth_address(VerifyingKey::from(&secret_key).to_encoded_point(false).as_bytes()).unwrap();
+    assert!(KeyType::Ethereum.with_hash_mode(HashMode::BitcoinMessage).is_valid(&api, data, &eth_signature, &address));
+
+    // wallets signing raw digests
+    let digest = HashMode::Keccak256.hash(data);
+    let signature: Signature = secret_key.sign_prehash_recoverable(&digest).unwrap().0;
+    let verifier = KeyType::Secp256k1.with_hash_mode(HashMode::Prehashed);
+    assert!(verifier.is_valid(&api, &digest, &signature.to_bytes(), public_key.as_bytes()));
+    // digests of secp256k1 must be 32 bytes long
+    assert!(KeyType::Secp256k1.with_hash_mode(HashMode::Sha512)
+        .verify(&api, data, &signature.to_bytes(), public_key.as_bytes()).is_err());
+
+    // ed25519 keys can sign any digests
+    let secret_key = Ed25519SigningKey::new(OsRng);
+    let public_key : [u8; 32] = Ed25519VerificationKey::from(&secret_key).into();
+    let signature = secret_key.sign(&HashMode::Sha512.hash(data));
+    let verifier = KeyType::Ed25519.with_hash_mode(HashMode::Sha512);
+    assert!(verifier.is_valid(&api, data, &signature.to_bytes(), &public_key));
+    assert_eq!(
+        verifier.verify_batch(&api, &[data.into()], &[signature.to_bytes().into()], &public_key).unwrap(),
+        vec![true]
+    );
+
+    // mode requested through the payload
+    let payload = AuthPayload {
+        hrp: None,
+        credential_id: None,
+        extension: Some(to_json_binary(&HashExtension { hash_mode: HashMode::Keccak256 }).unwrap()),
+        delegation_depth: None,
+    };
+    assert_eq!(HashMode::from_payload(Some(&payload)), Some(HashMode::Keccak256));
+    assert_eq!(HashMode::from_payload(None), None);
+}
@@ -0,0 +1,227 @@
+use types::wasm::{
+    from_json, to_json_binary, testing::{MockApi, MockQuerier, MockStorage}, Binary, ContractResult, Empty, QuerierWrapper, Storage,
+    SystemError, SystemResult, WasmQuery
+};
+use cw22::{set_contract_supported_interface, Cw22Error, Cw22QueryMsg, KnownInterface, SupportedInterfaceResponse};
+use cosmwasm_schema::cw_serde;
+use std::sync::{atomic::{AtomicUsize, Ordering}, Arc};
+
+use k256::{
+    ecdsa::{signature::DigestSigner, SigningKey, VerifyingKey, Signature},
+    elliptic_curve::rand_core::OsRng
+};
+use sha2::{Sha256, digest::{Update, Digest}};
+
+use cw81::{
+    query_valid_signature, AuthPayload, Cw81Contract, DelegatingVerifier,
+    Secp256k1Verifier, SignatureVerifier, ValidSignatureResponse, ValidSignaturesResponse
+};
+
+
+const MSG: &str = "Testing String!";
+
+
+#[cw_serde]
+enum NestedQueryMsg {
+    ValidSignature {
+        data: Binary,
+        signature: Binary,
+        payload: Option<AuthPayload>,
+    },
+}
+
+
+#[cw_serde]
+enum Cw81QueryMsg {
+    ValidSignature {
+        data: Binary,
+        signature: Binary,
+        payload: Option<Binary>,
+    },
+    ValidSignatures {
+        data: Vec<Binary>,
+        signatures: Vec<Binary>,
+        payload: Option<Binary>,
+    },
+}
+
+
+/// Contract approving the "approved" signature of `MSG` reached at the depth of 1 without a `credential_id`.
+/// The "with-payload" signature also needs the other fields of the original payload including its extension
+fn mock_querier(nested: String) -> MockQuerier {
+    let mut querier: MockQuerier = MockQuerier::new(&[]);
+    querier.update_wasm(move |query| match query {
+        WasmQuery::ContractInfo { contract_addr } if *contract_addr == nested => {
+            SystemResult::Ok(ContractResult::Ok(Binary::from(
+                br#"{"code_id":1,"creator":"creator","admin":null,"pinned":false,"ibc_port":null}"#
+            )))
+        },
+        WasmQuery::Smart { contract_addr, msg } if *contract_addr == nested => {
+            let NestedQueryMsg::ValidSignature { data, signature, payload } = from_json(msg).unwrap();
+            let payload = payload.unwrap_or(AuthPayload { credential_id: None, hrp: None, extension: None, delegation_depth: None });
+            let is_valid = match signature.as_slice() {
+                b"approved" => true,
+                b"with-payload" => payload.hrp.as_deref() == Some("cosmos") 
+                    && payload.extension == Some(Binary::from(b"custom")),
+                _ => false,
+            };
+            SystemResult::Ok(ContractResult::Ok(to_json_binary(&ValidSignatureResponse {
+                is_valid: is_valid 
+                    && data.as_slice() == MSG.as_bytes() 
+                    && payload.delegation_depth == Some(1) 
+                    && payload.credential_id.is_none(),
+            }).unwrap()))
+        },
+        _ => SystemResult::Err(SystemError::NoSuchContract { addr: "unknown".into() }),
+    });
+    querier
+}
+
+
+#[test]
+fn delegating_verifier() {
+    let api = MockApi::default();
+    let nested = api.addr_make("nested");
+    let querier = mock_querier(nested.to_string());
+    let querier = QuerierWrapper::<Empty>::new(&querier);
+
+    // direct query
+    let payload = AuthPayload {
+        credential_id: None,
+        hrp: None,
+        extension: None,
+        delegation_depth: Some(1),
+    };
+    assert!(query_valid_signature(&querier, nested.as_str(), MSG.as_bytes(), b"approved", Some(&payload)).unwrap());
+    assert!(!query_valid_signature(&querier, nested.as_str(), MSG.as_bytes(), b"approved", None::<&AuthPayload>).unwrap());
+
+    // credentials that are contracts are forwarded
+    let verifier = DelegatingVerifier::new(querier, Secp256k1Verifier, 2);
+    let contract_credential = nested.as_bytes();
+    assert!(verifier.verify(&api, MSG.as_bytes(), b"approved", contract_credential).unwrap());
+    assert!(!verifier.verify(&api, MSG.as_bytes(), b"rejected", contract_credential).unwrap());
+    assert!(verifier.verify(&api, MSG.as_bytes(), b"approved", api.addr_make("missing").as_bytes()).is_err());
+    // the original payload is needed by the nested contract
+    assert!(!verifier.verify(&api, MSG.as_bytes(), b"with-payload", contract_credential).unwrap());
+
+    // while the others are checked by the inner verifier
+    let secret_key = SigningKey::random(&mut OsRng);
+    let pubkey = VerifyingKey::from(&secret_key).to_encoded_point(true);
+    let signature: Signature = secret_key.sign_digest(Sha256::new().chain(MSG));
+    assert!(verifier.verify(&api, MSG.as_bytes(), &signature.to_bytes(), pubkey.as_bytes()).unwrap());
+
+    let res = verifier.verify_batch(
+        &api, 
+        &[MSG.as_bytes().into(), MSG.as_bytes().into()], 
+        &[Binary::from(b"approved"), signature.to_bytes().as_slice().into()], 
+        contract_credential
+    ).unwrap();
+    assert_eq!(res, vec![true, false]);
+
+    // fields of the caller's payload are forwarded except the credential that selected the contract
+    let verifier = DelegatingVerifier::from_payload(querier, Secp256k1Verifier, 2, Some(&AuthPayload {
+        credential_id: Some("key".into()),
+        hrp: Some("cosmos".into()),
+        extension: Some(Binary::from(b"custom")),
+        delegation_depth: None,
+    }));
+    assert_eq!(verifier.depth, 0);
+    assert!(verifier.verify(&api, MSG.as_bytes(), b"with-payload", contract_credential).unwrap());
+
+    // contract that is already being delegated to can't forward any further
+    let verifier = DelegatingVerifier::from_payload(querier, Secp256k1Verifier, 2, Some(&AuthPayload {
+        credential_id: None,
+        hrp: None,
+        extension: None,
+        delegation_depth: Some(2),
+    }));
+    assert_eq!(verifier.depth, 2);
+    assert!(verifier.verify(&api, MSG.as_bytes(), b"approved", contract_credential).is_err());
+    assert!(verifier.verify(&api, MSG.as_bytes(), &signature.to_bytes(), pubkey.as_bytes()).unwrap());
+}
+
+
+#[test]
+fn cw81_contract() {
+    let api = MockApi::default();
+    let [single, multi, none, legacy] = ["single", "multi", "none", "legacy"].map(|name| api.addr_make(name));
+
+    let storages = [
+        (single.to_string(), vec![KnownInterface::Cw81.supported_interface()]),
+        (multi.to_string(), vec![KnownInterface::Cw81.supported_interface(), KnownInterface::Cw81Multi.supported_interface()]),
+        (none.to_string(), vec![]),
+    ].map(|(addr, interfaces)| {
+        let mut storage = MockStorage::new();
+        set_contract_supported_interface(&mut storage, &interfaces).unwrap();
+        (addr, storage)
+    });
+    let multi_addr = multi.to_string();
+    let legacy_addr = legacy.to_string();
+    let queries = Arc::new(AtomicUsize::new(0));
+    let counter = queries.clone();
+
+    // the single contract doesn't have `ValidSignatures` and the multi one only answers it
+    let mut querier: MockQuerier = MockQuerier::new(&[]);
+    querier.update_wasm(move |query| {
+        counter.fetch_add(1, Ordering::Relaxed);
+        match query {
+            WasmQuery::Raw { contract_addr, key } => {
+                let value = storages
+                    .iter()
+                    .find(|(addr, _)| addr == contract_addr)
+                    .and_then(|(_, storage)| storage.get(key.as_slice()))
+                    .unwrap_or_default();
+                SystemResult::Ok(ContractResult::Ok(Binary::from(value)))
+            },
+            WasmQuery::Smart { contract_addr, msg } => {
+                // contract with a custom storage layout that declares cw81 under the legacy name
+                if let Ok(Cw22QueryMsg::SupportedInterface { name }) = from_json(msg) {
+                    let version = (*contract_addr == legacy_addr && name == "crates.io:cw81").then(|| "2.2.0".to_string());
+                    return SystemResult::Ok(ContractResult::Ok(to_json_binary(&SupportedInterfaceResponse { version }).unwrap()));
+                }
+                let res = match from_json(msg) {
+                    Ok(Cw81QueryMsg::ValidSignature { signature, .. }) if *contract_addr != multi_addr => {
+                        to_json_binary(&ValidSignatureResponse { is_valid: signature.as_slice() == b"approved" })
+                    },
+                    Ok(Cw81QueryMsg::ValidSignatures { signatures, .. }) if *contract_addr == multi_addr => {
+                        to_json_binary(&ValidSignaturesResponse {
+                            are_valid: signatures.iter().map(|s| s.as_slice() == b"approved").collect()
+                        })
+                    },
+                    _ => return SystemResult::Ok(ContractResult::Err("Unknown query".into())),
+                };
+                SystemResult::Ok(ContractResult::Ok(res.unwrap()))
+            },
+            _ => SystemResult::Err(SystemError::Unknown {}),
+        }
+    });
+    let querier = QuerierWrapper::<Empty>::new(&querier);
+
+    let data : Vec<Binary> = vec![MSG.as_bytes().into(), MSG.as_bytes().into()];
+    let signatures : Vec<Binary> = vec![b"approved".into(), b"rejected".into()];
+
+    let single = Cw81Contract::new(single);
+    assert!(single.is_supported(&querier).unwrap());
+    assert!(!single.is_multi(&querier).unwrap());
+    assert!(single.valid_signature(&querier, MSG.as_bytes(), b"approved", None::<&Binary>).unwrap());
+    assert!(!single.valid_signature(&querier, MSG.as_bytes(), b"rejected", None::<&Binary>).unwrap());
+    assert_eq!(single.valid_signatures(&querier, &data, &signatures, None::<&Binary>).unwrap(), vec![true, false]);
+
+    let multi = Cw81Contract::new(multi);
+    assert!(multi.is_multi(&querier).unwrap());
+    // one raw query for the declaration and one for the signatures
+    queries.store(0, Ordering::Relaxed);
+    assert_eq!(multi.valid_signatures(&querier, &data, &signatures, None::<&Binary>).unwrap(), vec![true, false]);
+    assert_eq!(queries.load(Ordering::Relaxed), 2);
+    assert!(multi.valid_signatures(&querier, &data, &signatures[..1], None::<&Binary>).is_err());
+
+    let legacy = Cw81Contract::new(legacy);
+    assert!(legacy.is_supported(&querier).unwrap());
+    assert!(!legacy.is_multi(&querier).unwrap());
+    assert_eq!(legacy.valid_signatures(&querier, &data, &signatures, None::<&Binary>).unwrap(), vec![true, false]);
+
+    let none = Cw81Contract::new(none);
+    assert!(!none.is_supported(&querier).unwrap());
+    let err = none.valid_signature(&querier, MSG.as_bytes(), b"approved", None::<&Binary>).unwrap_err();
+    assert!(matches!(err, Cw22Error::NotSupported { .. }));
+}
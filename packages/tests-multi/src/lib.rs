@@ -2,4 +2,7 @@
 mod curves;
 
 #[cfg(test)]
-mod msgs;
\ No newline at end of file
+mod msgs;
+
+#[cfg(test)]
+mod delegate;
\ No newline at end of file
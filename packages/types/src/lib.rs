@@ -27,6 +27,9 @@ pub use {
 pub use protos::{
     registry_execute, 
     account_execute, 
+    supported_interface_query,
+    supports,
+    valid_signature_detailed_query,
 };
 
 use cosmwasm_schema::cw_serde;
@@ -57,7 +60,16 @@ pub struct CanExecuteSignedResponse {
 }
 
 
-#[cw_serde]
+// types of older versions and of Secret Network don't implement `Schemaifier` required by `cw_serde`
+#[cfg_attr(feature = "cosmwasm", cw_serde)]
+#[cfg_attr(not(feature = "cosmwasm"), derive(
+    cosmwasm_schema::serde::Serialize,
+    cosmwasm_schema::serde::Deserialize,
+    Clone, Debug, PartialEq,
+    cosmwasm_schema::schemars::JsonSchema,
+))]
+#[cfg_attr(not(feature = "cosmwasm"), serde(crate = "cosmwasm_schema::serde"))]
+#[cfg_attr(not(feature = "cosmwasm"), schemars(crate = "cosmwasm_schema::schemars"))]
 pub struct AuthPayload {
     /// Which credential to use if multiple are available
     pub credential_id   :   Option<String>,
@@ -65,9 +77,20 @@ pub struct AuthPayload {
     pub hrp             :   Option<String>,
     /// Additional arguments to pass depending on a credential in question
     pub extension       :   Option<wasm::Binary>,
+    /// Number of contracts the signature has been forwarded through by a delegating verifier
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delegation_depth :  Option<u32>,
 }
 
-#[cw_serde]
+#[cfg_attr(feature = "cosmwasm", cw_serde)]
+#[cfg_attr(not(feature = "cosmwasm"), derive(
+    cosmwasm_schema::serde::Serialize,
+    cosmwasm_schema::serde::Deserialize,
+    Clone, Debug, PartialEq,
+    cosmwasm_schema::schemars::JsonSchema,
+))]
+#[cfg_attr(not(feature = "cosmwasm"), serde(crate = "cosmwasm_schema::serde"))]
+#[cfg_attr(not(feature = "cosmwasm"), schemars(crate = "cosmwasm_schema::schemars"))]
 pub struct SignedDataMsg {
     /// Base64 encoded JSON string of replay envelope, serialized actions messages, both of them or none of them
     pub data        :   wasm::Binary,
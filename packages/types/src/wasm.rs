@@ -5,5 +5,20 @@ pub use {cosmwasm_std_one as cosmwasm_std, cw_storage_plus_one as cw_storage_plu
 #[cfg(all(feature = "secretwasm", not(feature = "cosmwasm"), not(feature = "cosmwasm_v1")))]
 pub use secretwasm_std as cosmwasm_std;
 #[cfg(any(feature = "cosmwasm", feature = "cosmwasm_v1"))]
-pub use {cw_storage_plus::{Map, Item}, cosmwasm_std::to_json_binary};
-pub use cosmwasm_std::*;
\ No newline at end of file
+pub use {cw_storage_plus::{Map, Item, Bound}, cosmwasm_std::to_json_binary};
+// secret std predates the `*_json_*` renames of the serialization helpers
+#[cfg(all(feature = "secretwasm", not(feature = "cosmwasm"), not(feature = "cosmwasm_v1")))]
+pub use cosmwasm_std::{from_slice as from_json, to_binary as to_json_binary, to_vec as to_json_vec};
+pub use cosmwasm_std::*;
+
+/// Generic error with a custom message constructed the same way across the supported `StdError` versions
+#[cfg(feature = "cosmwasm")]
+pub fn generic_err(msg: impl Into<String>) -> StdError {
+    StdError::msg(msg.into())
+}
+
+/// Generic error with a custom message constructed the same way across the supported `StdError` versions
+#[cfg(not(feature = "cosmwasm"))]
+pub fn generic_err(msg: impl Into<String>) -> StdError {
+    StdError::generic_err(msg)
+}